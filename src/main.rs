@@ -3,25 +3,31 @@
 // Licensed under GPL-3.0-or-later. See LICENSE in project root.
 
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use nix::sys::signal::Signal;
 use obfstr::obfstr;
 use ratatui::{
     Frame, Terminal,
-    backend::{Backend, CrosstermBackend},
-    layout::{Constraint, Direction, Layout},
+    backend::{Backend, CrosstermBackend, TestBackend},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     symbols,
     text::{Line, Span},
     widgets::{
-        Axis, Block, Borders, Cell, Chart, Clear, Dataset, Paragraph, Row, Table, TableState,
+        Axis, Block, Borders, Cell, Chart, Clear, Dataset, GraphType, Paragraph, Row, Table,
+        TableState,
     },
 };
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashSet, VecDeque},
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     env,
     error::Error,
     fs, io,
@@ -35,6 +41,8 @@ use monitor::SystemMonitor;
 mod theme;
 use theme::{Theme, ThemeKind};
 mod plugin;
+mod screensaver;
+mod snapshot;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const NAME: &str = env!("CARGO_PKG_NAME");
@@ -43,20 +51,438 @@ const NAME: &str = env!("CARGO_PKG_NAME");
 enum SortKey {
     Cpu,
     Mem,
+    Virt,
+    Shr,
+    DiskRead,
+    DiskWrite,
     Pid,
+    Ppid,
     User,
     Command,
+    Deviation,
 }
 
-fn load_config_file_with_flag() -> (AppConfig, bool, PathBuf, ConfigSource) {
+// Samples kept per PID for the deviation sort
+const PROC_HISTORY_LEN: usize = 10;
+
+fn load_config_file_with_flag() -> (AppConfig, bool, PathBuf, ConfigSource, Option<String>) {
     let (path, source) = resolve_config_path();
     let existed = path.exists();
-    if let Ok(content) = fs::read_to_string(&path)
-        && let Ok(cfg) = toml::from_str::<AppConfig>(&content)
-    {
-        return (cfg, existed, path, source);
+    if let Ok(content) = fs::read_to_string(&path) {
+        match parse_config(&content) {
+            Ok((cfg, migrated_from, unknown)) => {
+                let mut warning = unknown
+                    .map(|keys| format!("Config: ignored unknown key(s): {}", keys.join(", ")));
+                if let Some(from) = migrated_from {
+                    // Keep the original (comments and all) before rewriting in the new schema
+                    let backup = path.with_extension(format!("toml.v{}.bak", from));
+                    let upgraded =
+                        fs::copy(&path, &backup).and_then(|_| save_config_file_at(&path, &cfg));
+                    warning = Some(match upgraded {
+                        Ok(()) => format!(
+                            "Config upgraded v{} -> v{} (backup: {})",
+                            from,
+                            CONFIG_VERSION,
+                            backup.display()
+                        ),
+                        Err(e) => format!("Config upgrade not saved: {}", e),
+                    });
+                }
+                return (cfg, existed, path, source, warning);
+            }
+            Err(reason) => {
+                let warning = format!("Config invalid, using defaults: {}", reason);
+                return (AppConfig::default(), existed, path, source, Some(warning));
+            }
+        }
+    }
+    (AppConfig::default(), existed, path, source, None)
+}
+
+// Current AppConfig schema; bump it and add a CONFIG_MIGRATIONS step when keys are renamed
+const CONFIG_VERSION: u32 = 1;
+
+// Upgrade steps as (version reached, [(old key, new key)]), applied in order
+const CONFIG_MIGRATIONS: &[(u32, &[(&str, &str)])] = &[
+    // v1: schema versioning introduced, no renames
+    (1, &[]),
+];
+
+// Upgrade a raw config table in place; returns the starting version if anything changed
+fn migrate_config(raw: &mut toml::Table) -> Option<u32> {
+    let from = raw
+        .get("config_version")
+        .and_then(|v| v.as_integer())
+        .unwrap_or(0) as u32;
+    if from >= CONFIG_VERSION {
+        return None;
+    }
+    for (version, renames) in CONFIG_MIGRATIONS {
+        if *version <= from {
+            continue;
+        }
+        for (old, new) in renames.iter() {
+            if let Some(value) = raw.remove(*old) {
+                // A value already under the new name wins over the legacy one
+                raw.entry(new.to_string()).or_insert(value);
+            }
+        }
+    }
+    raw.insert(
+        "config_version".to_string(),
+        toml::Value::Integer(CONFIG_VERSION as i64),
+    );
+    Some(from)
+}
+
+// The config, the version it was upgraded from, and unknown keys
+type ParsedConfig = (AppConfig, Option<u32>, Option<Vec<String>>);
+
+// Parse (and migrate) config text
+fn parse_config(content: &str) -> Result<ParsedConfig, String> {
+    let mut raw = content
+        .parse::<toml::Table>()
+        .map_err(|e| e.message().to_string())?;
+    let migrated_from = migrate_config(&mut raw);
+    let cfg: AppConfig = toml::Value::Table(raw.clone())
+        .try_into()
+        .map_err(|e: toml::de::Error| e.message().to_string())?;
+    let unknown = unknown_config_keys(&raw, &cfg);
+    // A named refresh profile wins over the individual rate fields
+    let cfg = match cfg.refresh_profile.clone() {
+        Some(name) => {
+            let mut cfg = cfg;
+            apply_refresh_profile(&mut cfg, &name);
+            cfg
+        }
+        None => cfg,
+    };
+    Ok((cfg, migrated_from, unknown))
+}
+
+// (name, ui_rate_ms, data_rate_ms, history_len, cpu_samples); cpu_samples is the smoothing
+// knob, and history_len * data_rate_ms is how far back the charts reach
+const REFRESH_PROFILES: &[(&str, u64, u64, usize, usize)] = &[
+    ("performance", 250, 1000, 60, 1),
+    ("balanced", 500, 5000, 30, 1),
+    ("battery", 2000, 15000, 20, 4),
+];
+
+// Set the rate fields from a named profile; false (config untouched) for unknown names
+fn apply_refresh_profile(cfg: &mut AppConfig, name: &str) -> bool {
+    let Some(&(name, ui_ms, data_ms, history, samples)) =
+        REFRESH_PROFILES.iter().find(|(n, ..)| *n == name)
+    else {
+        return false;
+    };
+    cfg.ui_rate_ms = ui_ms;
+    cfg.data_rate_ms = data_ms;
+    cfg.history_len = history;
+    cfg.cpu_samples = samples;
+    cfg.refresh_profile = Some(name.to_string());
+    true
+}
+
+// Top-level keys serde silently skipped (typos, or options from a newer version)
+fn unknown_config_keys(raw: &toml::Table, cfg: &AppConfig) -> Option<Vec<String>> {
+    // Every key that was parsed is present again after a round-trip; the rest were ignored
+    let known = toml::Value::try_from(cfg).ok()?;
+    let known = known.as_table()?;
+    let unknown: Vec<String> = raw
+        .keys()
+        .filter(|k| !known.contains_key(*k))
+        .cloned()
+        .collect();
+    if unknown.is_empty() {
+        None
+    } else {
+        Some(unknown)
+    }
+}
+
+// Fields listed by the Setup config editor, in display order (config_version is internal)
+const CONFIG_EDITOR_FIELDS: &[&str] = &[
+    "ui_rate_ms",
+    "data_rate_ms",
+    "process_rate_ms",
+    "max_rows",
+    "show_charts",
+    "theme",
+    "sort",
+    "sort_reversed",
+    "match_mode",
+    "kill_wait_ms",
+    "use_emoji",
+    "startup_overlay",
+    "startup_commands",
+    "idle_timeout_secs",
+    "idle_rate_ms",
+    "warn_percent",
+    "critical_percent",
+    "cpu_samples",
+    "history_len",
+    "insights_top_n",
+    "renice_confirm_below",
+    "show_parent_name",
+    "auto_theme",
+    "day_theme",
+    "night_theme",
+    "day_start_hour",
+    "night_start_hour",
+    "export_compress",
+    "export_max_command_chars",
+    "show_cgroup",
+    "network_skip_interfaces",
+    "header_fields",
+    "disk_show_pseudo",
+    "cpu_group_siblings",
+    "quiet_mode",
+    "quiet_cpu_percent",
+    "quiet_mem_percent",
+    "manual_refresh",
+    "chart_marker",
+    "chart_style",
+    "refresh_profile",
+    "keep_selection_on_sort",
+    "sort_jump_to_top",
+    "chart_redline_percent",
+    "screensaver",
+    "screensaver_after_secs",
+    "show_process_chart",
+    "chart_swap",
+    "theme_cycle",
+    "confirm_kill",
+    "quick_kill",
+    "percent_decimals",
+];
+
+// Current value of a config field as TOML text, None when an optional field is unset
+fn config_field_value(cfg: &AppConfig, key: &str) -> Option<String> {
+    let value = toml::Value::try_from(cfg).ok()?;
+    value.get(key).map(|v| v.to_string())
+}
+
+// Set one field from editor input: a TOML value, a bare word for strings, or empty for the default
+fn set_config_field(cfg: &AppConfig, key: &str, input: &str) -> Result<AppConfig, String> {
+    let toml::Value::Table(mut table) = toml::Value::try_from(cfg).map_err(|e| e.to_string())?
+    else {
+        return Err("config is not a table".to_string());
+    };
+    let input = input.trim();
+    if input.is_empty() {
+        table.remove(key);
+    } else {
+        let value = format!("v = {}", input)
+            .parse::<toml::Table>()
+            .ok()
+            .and_then(|mut t| t.remove("v"))
+            .unwrap_or_else(|| toml::Value::String(input.to_string()));
+        table.insert(key.to_string(), value);
+    }
+    toml::Value::Table(table)
+        .try_into()
+        .map_err(|e: toml::de::Error| format!("{}: {}", key, e.message()))
+}
+
+// Make processes safe to share: users become user1, user2... (stable within one export),
+// commands shrink to the binary name and container/cgroup labels are dropped
+fn anonymize_processes(procs: &mut [monitor::ProcessInfo]) {
+    let mut users: HashMap<String, String> = HashMap::new();
+    for p in procs.iter_mut() {
+        let n = users.len() + 1;
+        p.user = users
+            .entry(std::mem::take(&mut p.user))
+            .or_insert_with(|| format!("user{}", n))
+            .clone();
+        // Kernel threads have no argv; their name (e.g. "kworker/0:1") reveals nothing
+        if let Some(program) = p.args.first() {
+            let binary = Path::new(program)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            p.command = binary.clone();
+            p.args = vec![binary];
+        }
+        p.cgroup.clear();
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum MemoryPressureLevel {
+    None,
+    Moderate,
+    Severe,
+}
+
+// Real memory pressure: tasks stalling (PSI) or pages moving through swap. Swap-out alone
+// can be the kernel parking cold pages; swap-in means something needed them back.
+fn memory_pressure_level(
+    swap_in: f64,
+    swap_out: f64,
+    psi: Option<monitor::MemoryPressure>,
+) -> MemoryPressureLevel {
+    let psi = psi.unwrap_or_default();
+    if psi.full_avg10 >= 5.0 || psi.some_avg10 >= 20.0 || swap_in + swap_out >= 1000.0 {
+        MemoryPressureLevel::Severe
+    } else if psi.some_avg10 >= 5.0 || swap_in >= 100.0 {
+        MemoryPressureLevel::Moderate
+    } else {
+        MemoryPressureLevel::None
+    }
+}
+
+fn sort_key_from_str(name: &str) -> Option<SortKey> {
+    match name {
+        "cpu" => Some(SortKey::Cpu),
+        // RES was its own key once, but MEM already is the resident set
+        "mem" | "res" => Some(SortKey::Mem),
+        "virt" => Some(SortKey::Virt),
+        "shr" => Some(SortKey::Shr),
+        "disk_read" => Some(SortKey::DiskRead),
+        "disk_write" => Some(SortKey::DiskWrite),
+        "pid" => Some(SortKey::Pid),
+        "ppid" => Some(SortKey::Ppid),
+        "user" => Some(SortKey::User),
+        "command" => Some(SortKey::Command),
+        "deviation" => Some(SortKey::Deviation),
+        _ => None,
+    }
+}
+
+fn chart_marker_from_str(name: &str) -> Option<symbols::Marker> {
+    match name {
+        "dot" => Some(symbols::Marker::Dot),
+        "braille" => Some(symbols::Marker::Braille),
+        "block" => Some(symbols::Marker::Block),
+        "halfblock" => Some(symbols::Marker::HalfBlock),
+        "bar" => Some(symbols::Marker::Bar),
+        _ => None,
+    }
+}
+
+fn theme_kind_from_str(name: &str) -> Option<ThemeKind> {
+    match name {
+        "dark" => Some(ThemeKind::Dark),
+        "stellar" => Some(ThemeKind::Stellar),
+        "matrix" => Some(ThemeKind::Matrix),
+        _ => None,
+    }
+}
+
+// How filter chips and the search term match command lines and users (Tab in F3/F4)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum MatchMode {
+    Substring,
+    Regex,
+    // Term characters appear in order, not necessarily adjacent ("ffx" hits "firefox")
+    Fuzzy,
+}
+
+impl MatchMode {
+    fn from_config(name: Option<&str>) -> Self {
+        match name {
+            Some("regex") => MatchMode::Regex,
+            Some("fuzzy") => MatchMode::Fuzzy,
+            _ => MatchMode::Substring,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            MatchMode::Substring => "substring",
+            MatchMode::Regex => "regex",
+            MatchMode::Fuzzy => "fuzzy",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            MatchMode::Substring => MatchMode::Regex,
+            MatchMode::Regex => MatchMode::Fuzzy,
+            MatchMode::Fuzzy => MatchMode::Substring,
+        }
+    }
+}
+
+// One search/filter term compiled for the active match mode
+enum TermMatcher {
+    Substring(String),
+    Regex(regex::Regex),
+    Fuzzy(String),
+}
+
+impl TermMatcher {
+    fn new(term: &str, mode: MatchMode) -> Result<Self, regex::Error> {
+        Ok(match mode {
+            MatchMode::Substring => TermMatcher::Substring(term.to_lowercase()),
+            MatchMode::Regex => TermMatcher::Regex(
+                regex::RegexBuilder::new(term)
+                    .case_insensitive(true)
+                    .build()?,
+            ),
+            MatchMode::Fuzzy => TermMatcher::Fuzzy(term.to_lowercase()),
+        })
+    }
+
+    // A regex that no longer compiles (mode switched after it was added) matches literally
+    fn new_or_literal(term: &str, mode: MatchMode) -> Self {
+        Self::new(term, mode).unwrap_or_else(|_| TermMatcher::Substring(term.to_lowercase()))
+    }
+
+    // Byte ranges of the match in `text` (for highlighting), None when it doesn't match
+    fn find(&self, text: &str) -> Option<Vec<(usize, usize)>> {
+        match self {
+            TermMatcher::Substring(term) => {
+                // Lowercasing can change byte lengths outside ASCII; only trust aligned hits
+                let start = text.to_lowercase().find(term.as_str())?;
+                let end = start + term.len();
+                if text.is_char_boundary(start) && text.is_char_boundary(end.min(text.len())) {
+                    Some(vec![(start, end.min(text.len()))])
+                } else {
+                    Some(Vec::new())
+                }
+            }
+            TermMatcher::Regex(re) => re.find(text).map(|m| vec![(m.start(), m.end())]),
+            TermMatcher::Fuzzy(term) => {
+                let mut wanted = term.chars().peekable();
+                let mut ranges = Vec::new();
+                for (i, c) in text.char_indices() {
+                    let Some(&w) = wanted.peek() else {
+                        break;
+                    };
+                    if c.to_lowercase().eq(w.to_lowercase()) {
+                        ranges.push((i, i + c.len_utf8()));
+                        wanted.next();
+                    }
+                }
+                wanted.peek().is_none().then_some(ranges)
+            }
+        }
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        self.find(text).is_some()
+    }
+}
+
+// Command text with the matched ranges picked out
+fn highlight_matches(text: &str, ranges: &[(usize, usize)], style: Style) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for &(start, end) in ranges {
+        if start < pos {
+            continue;
+        }
+        if start > pos {
+            spans.push(Span::raw(text[pos..start].to_string()));
+        }
+        spans.push(Span::styled(text[start..end].to_string(), style));
+        pos = end;
     }
-    (AppConfig::default(), existed, path, source)
+    if pos < text.len() {
+        spans.push(Span::raw(text[pos..].to_string()));
+    }
+    Line::from(spans)
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -69,8 +495,131 @@ enum Overlay {
     #[allow(dead_code)]
     Export,
     Insights,
+    Detail,
+    Events,
+    Confirm,
+    ExportMenu,
+    ConfigEditor,
+    SystemInfo,
+    Signal,
+    // 'N': nice value prompt for renicing everything the search/filter matches
+    BatchNice,
+    // 'n': per-interface network rates and totals
+    Network,
+    // 'd': usage of each mounted filesystem
+    Disks,
+}
+
+// Shift+F9 signal picker entries, with what each does to the target
+const SIGNAL_CHOICES: &[(Signal, &str)] = &[
+    (Signal::SIGTERM, "terminate politely (same as F9)"),
+    (Signal::SIGKILL, "kill immediately, cannot be caught"),
+    (Signal::SIGHUP, "hang up; many daemons reload config"),
+    (Signal::SIGINT, "interrupt, like Ctrl+C"),
+    (Signal::SIGSTOP, "pause the process"),
+    (Signal::SIGCONT, "resume a paused process"),
+];
+
+// Typed signal in the Shift+F9 picker: a number ("10") or a name with or without the
+// SIG prefix, in any case ("SIGUSR1", "usr1")
+fn parse_signal(input: &str) -> Result<Signal, String> {
+    let input = input.trim();
+    if let Ok(n) = input.parse::<i32>() {
+        return Signal::try_from(n).map_err(|_| format!("No signal numbered {}", n));
+    }
+    let name = input.to_uppercase();
+    let name = if name.starts_with("SIG") {
+        name
+    } else {
+        format!("SIG{}", name)
+    };
+    name.parse::<Signal>()
+        .map_err(|_| format!("Unknown signal: {}", input))
+}
+
+// Lifecycle of an F11 export as shown in the Export overlay
+#[derive(Clone, Debug)]
+enum ExportState {
+    Idle,
+    Running {
+        started: Instant,
+    },
+    Done {
+        path: String,
+        bytes: u64,
+        elapsed: Duration,
+    },
+    Failed(String),
+}
+
+// Deferred export write, run via spawn_blocking so slow storage never stalls the UI
+type ExportJob = Box<dyn FnOnce() -> Result<String, String> + Send>;
+
+// Where F11 sends a snapshot: the built-in JSON writer or a plugin-provided format
+#[derive(Clone, Debug, PartialEq)]
+enum ExportTarget {
+    Json,
+    Plugin { name: String, format: String },
 }
 
+// Action waiting for a y/n answer in the confirmation dialog
+#[derive(Clone, Debug)]
+enum PendingAction {
+    Renice {
+        pid: u32,
+        from: i32,
+        to: i32,
+    },
+    // SIGTERM, then offer to re-run the captured command line if nothing respawns it
+    Restart {
+        pid: u32,
+        args: Vec<String>,
+        cwd: Option<PathBuf>,
+    },
+    Reexec {
+        args: Vec<String>,
+        cwd: Option<PathBuf>,
+    },
+    // Setup 'D': overwrite the active config file with AppConfig::default()
+    ResetConfig {
+        path: PathBuf,
+    },
+    // F9 with confirm_kill: SIGTERM the process captured when the dialog opened
+    Kill {
+        pid: u32,
+        user: String,
+        command: String,
+    },
+    // 'N': renice every process matching the search/filter, captured when the prompt closed
+    BatchRenice {
+        nice: i32,
+        targets: Vec<(u32, String)>,
+    },
+    // Setup Enter on a missing candidate: show what would be written before creating it
+    CreateConfig {
+        path: PathBuf,
+        source: ConfigSource,
+    },
+}
+
+// Restart in progress: waiting for the old process to exit and maybe come back
+#[derive(Clone, Debug)]
+struct PendingRestart {
+    pid: u32,
+    args: Vec<String>,
+    cwd: Option<PathBuf>,
+    sent_at: Instant,
+    exited_at: Option<Instant>,
+    // Same-argv processes already running at SIGTERM time (sibling workers, other shells);
+    // none of them counts as the respawn
+    preexisting: Vec<u32>,
+}
+
+const SCREENSAVER_FRAME_MS: u64 = 80;
+
+// How long to wait after exit for a supervisor (systemd, runit, a parent loop) to respawn
+const RESPAWN_GRACE: Duration = Duration::from_secs(2);
+
 fn print_help() {
     println!(
         "🌟 {} v{} - An optimized monitoring system linux",
@@ -83,6 +632,10 @@ fn print_help() {
     println!("OPTIONS:");
     println!("    -h, --help       Show this help message");
     println!("    -V, --version    Show version information");
+    println!("    --diff <a> <b>   Compare two exported snapshots (.json or .json.gz)");
+    println!("    --anonymize      Exports hide users, arguments and hostname (safe to share)");
+    println!("    --cgroup <path>  Only show processes in a cgroup/slice, with group totals");
+    println!("    --selftest       Render one frame off-screen and check it (no TTY needed)");
     println!();
     println!("DESCRIPTION:");
     println!("    Futuristic TUI system monitor with AI-powered insights");
@@ -92,6 +645,10 @@ fn print_help() {
     println!();
     println!("EXAMPLES:");
     println!("    {} --help       Show this help message", NAME);
+    println!(
+        "    {} --diff before.json during.json   Show what changed between snapshots",
+        NAME
+    );
     println!();
     println!();
     println!("KEYBOARD SHORTCUTS:");
@@ -121,6 +678,121 @@ fn print_version() {
     println!("{} {}", NAME, VERSION);
 }
 
+// Draw one frame into an off-screen buffer and return its cells as a single string
+fn render_to_text(app: &App, width: u16, height: u16) -> Result<String, String> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).map_err(|e| e.to_string())?;
+    terminal
+        .draw(|f| ui(f, app))
+        .map_err(|e| format!("draw failed: {}", e))?;
+    Ok(terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect())
+}
+
+// An app on `config` with one full data pass. Its config path points at an empty temp
+// directory, so the user's config file and plugins.toml are neither read nor written
+fn detached_app(config: AppConfig) -> App {
+    let path = env::temp_dir().join("lyvoxa-detached").join("config.toml");
+    let mut app = App::with_config(config, path, ConfigSource::Env, None, Vec::new());
+    app.update_slow();
+    app.update_processes();
+    app.update_fast();
+    app.rebuild_process_view();
+    app
+}
+
+// --selftest: one full data pass, then draw the real UI into an in-memory buffer at a roomy
+// and a cramped size and look for the landmarks every layout should have. Uses built-in
+// defaults rather than the user's config so results don't depend on local settings
+fn run_selftest() -> bool {
+    let app = detached_app(AppConfig::default());
+
+    let mut failures = Vec::new();
+    for (width, height) in [(120u16, 40u16), (80, 24)] {
+        let text = match render_to_text(&app, width, height) {
+            Ok(t) => t,
+            Err(e) => {
+                failures.push(format!("{}x{}: {}", width, height, e));
+                continue;
+            }
+        };
+        for landmark in ["Lyvoxa", "Mem[", "Swp[", "PID", "COMMAND"] {
+            if !text.contains(landmark) {
+                failures.push(format!("{}x{}: missing {:?}", width, height, landmark));
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        println!(
+            "{} {} selftest: OK ({} processes)",
+            NAME,
+            VERSION,
+            app.processes.len()
+        );
+        true
+    } else {
+        println!("{} {} selftest: FAILED", NAME, VERSION);
+        for f in &failures {
+            println!("  {}", f);
+        }
+        false
+    }
+}
+
+// Run refresh() + get_processes() n times and print min/avg/max per phase
+fn run_bench_refresh(n: usize) {
+    let mut monitor = SystemMonitor::new();
+    // Prime once so the first timed pass isn't paying for initial discovery
+    monitor.refresh();
+    let mut refresh_ms = Vec::with_capacity(n);
+    let mut collect_ms = Vec::with_capacity(n);
+    let mut count = 0usize;
+    for _ in 0..n {
+        let t0 = Instant::now();
+        monitor.refresh();
+        let t1 = Instant::now();
+        count = monitor.get_processes().len();
+        let t2 = Instant::now();
+        refresh_ms.push((t1 - t0).as_secs_f64() * 1000.0);
+        collect_ms.push((t2 - t1).as_secs_f64() * 1000.0);
+    }
+    let total_ms: Vec<f64> = refresh_ms
+        .iter()
+        .zip(&collect_ms)
+        .map(|(r, c)| r + c)
+        .collect();
+    let stats = |v: &[f64]| {
+        let min = v.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = v.iter().cloned().fold(0.0, f64::max);
+        let avg = v.iter().sum::<f64>() / v.len() as f64;
+        (min, avg, max)
+    };
+    println!("{} {} refresh benchmark", NAME, VERSION);
+    println!(
+        "iterations: {}   cpus: {}   processes: {}",
+        n,
+        monitor.get_cpu_count(),
+        count
+    );
+    println!(
+        "{:<10} {:>10} {:>10} {:>10}",
+        "phase", "min ms", "avg ms", "max ms"
+    );
+    for (label, v) in [
+        ("refresh", &refresh_ms),
+        ("collect", &collect_ms),
+        ("total", &total_ms),
+    ] {
+        let (min, avg, max) = stats(v);
+        println!("{:<10} {:>10.2} {:>10.2} {:>10.2}", label, min, avg, max);
+    }
+}
+
 // Disable core dumps to make memory dumping harder (Linux only)
 #[cfg(target_os = "linux")]
 fn harden_process() {
@@ -148,6 +820,50 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 print_version();
                 return Ok(());
             }
+            "--diff" => {
+                let (Some(a), Some(b)) = (args.get(2), args.get(3)) else {
+                    eprintln!("Usage: {} --diff <before.json> <after.json>", NAME);
+                    std::process::exit(1);
+                };
+                match (
+                    snapshot::Snapshot::load(Path::new(a)),
+                    snapshot::Snapshot::load(Path::new(b)),
+                ) {
+                    (Ok(before), Ok(after)) => {
+                        println!("{}", snapshot::format_diff(&before, &after));
+                        return Ok(());
+                    }
+                    (Err(e), _) | (_, Err(e)) => {
+                        eprintln!("Failed to load snapshot: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            // Handled when the app is created
+            "--anonymize" => {}
+            "--cgroup" if args.get(2).is_some() => {}
+            "--cgroup" => {
+                eprintln!(
+                    "Usage: {} --cgroup <path>  (e.g. system.slice/nginx.service)",
+                    NAME
+                );
+                std::process::exit(1);
+            }
+            "--selftest" => {
+                std::process::exit(if run_selftest() { 0 } else { 1 });
+            }
+            // Hidden self-diagnostic: time the refresh/collect cycle for bug reports
+            "--bench-refresh" => {
+                let n = match args.get(2).map(|s| s.parse::<usize>()) {
+                    Some(Ok(n)) if n > 0 => n,
+                    _ => {
+                        eprintln!("Usage: {} --bench-refresh <n>  (n >= 1)", NAME);
+                        std::process::exit(1);
+                    }
+                };
+                run_bench_refresh(n);
+                return Ok(());
+            }
             _ => {
                 eprintln!("Unknown option: {}", args[1]);
                 eprintln!("Use --help for usage information");
@@ -165,7 +881,15 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app and run it
-    let app = App::new();
+    let mut app = App::new();
+    app.anonymize_exports = args.iter().any(|a| a == "--anonymize");
+    if let Some(scope) = args
+        .iter()
+        .position(|a| a == "--cgroup")
+        .and_then(|i| args.get(i + 1))
+    {
+        app.monitor.set_cgroup_scope(Some(scope));
+    }
     let res = run_app(&mut terminal, app).await;
 
     // Restore terminal
@@ -189,25 +913,335 @@ async fn main() -> Result<(), Box<dyn Error>> {
 //  - LYVOXA_DATA_MS: System data refresh interval (ms), default 5000
 //  - LYVOXA_ROWS: Max process rows displayed, default 15
 //  - LYVOXA_SHOW_CHARTS: "1"/"true" to show charts (default), "0"/"false" to hide
+// Missing keys fall back to AppConfig::default(), so partial configs only override what they set;
+// unknown keys are ignored (reported via the status line) rather than rejecting the file
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 struct AppConfig {
+    // Schema version for migrations (absent = pre-versioning, treated as 0)
+    #[serde(default)]
+    config_version: u32,
+    #[serde(default = "default_ui_rate_ms")]
     ui_rate_ms: u64,
+    #[serde(default = "default_data_rate_ms")]
     data_rate_ms: u64,
+    // Process list refresh, separate from data_rate_ms so the full procfs walk can run less
+    // often than the disk/swap metrics; unset = same as data_rate_ms
+    #[serde(default)]
+    process_rate_ms: Option<u64>,
+    #[serde(default = "default_max_rows")]
     max_rows: usize,
+    #[serde(default = "default_show_charts")]
     show_charts: bool,
+    #[serde(default)]
     theme: Option<String>,
+    #[serde(default)]
     sort: Option<String>,
+    // Flip the sort key's natural order (biggest-first for usage columns, A-Z/lowest-first
+    // for PID, user and command); toggled with 'o' or Shift+F6
+    #[serde(default)]
+    sort_reversed: bool,
+    // "substring" (default), "regex" or "fuzzy" for F3 search and F4 filters; Tab in either
+    // prompt cycles it
+    #[serde(default)]
+    match_mode: Option<String>,
+    // How long F9 waits for a process to exit before offering SIGKILL (0 = fire-and-forget)
+    #[serde(default = "default_kill_wait_ms")]
+    kill_wait_ms: u64,
+    // Emoji in the TUI: true/false explicit, unset = auto-detect from locale/terminal
+    #[serde(default)]
+    use_emoji: Option<bool>,
+    // Overlay to open on launch: "help", "setup", "search", "filter" or "insights"
+    #[serde(default)]
+    startup_overlay: Option<String>,
+    // Session-only commands run once on launch, e.g. ["filter=nginx", "sort=mem", "charts=off"]
+    #[serde(default)]
+    startup_commands: Vec<String>,
+    // Power saving: after this many seconds without input, refresh at idle_rate_ms (0 = never)
+    #[serde(default = "default_idle_timeout_secs")]
+    idle_timeout_secs: u64,
+    #[serde(default = "default_idle_rate_ms")]
+    idle_rate_ms: u64,
+    // Gauge severity thresholds (percent)
+    #[serde(default = "default_warn_percent")]
+    warn_percent: f64,
+    #[serde(default = "default_critical_percent")]
+    critical_percent: f64,
+    // CPU sub-samples averaged into each displayed reading (1 = single sample)
+    #[serde(default = "default_cpu_samples")]
+    cpu_samples: usize,
+    // Samples kept for the charts and sparklines
+    #[serde(default = "default_history_len")]
+    history_len: usize,
+    // Processes considered and listed by F12 insights
+    #[serde(default = "default_insights_top_n")]
+    insights_top_n: usize,
+    // F7 asks for confirmation before renicing a process below this nice value
+    #[serde(default)]
+    renice_confirm_below: i32,
+    // Append the parent's command name to the PPID column
+    #[serde(default)]
+    show_parent_name: bool,
+    // Switch between day_theme and night_theme at the configured local hours
+    #[serde(default)]
+    auto_theme: bool,
+    #[serde(default = "default_day_theme")]
+    day_theme: String,
+    #[serde(default = "default_night_theme")]
+    night_theme: String,
+    #[serde(default = "default_day_start_hour")]
+    day_start_hour: u32,
+    #[serde(default = "default_night_start_hour")]
+    night_start_hour: u32,
+    // Gzip snapshot exports (.json.gz)
+    #[serde(default)]
+    export_compress: bool,
+    // Cut exported command lines to this many characters (0 = no limit); independent of
+    // the on-screen truncation, and applied after --anonymize
+    #[serde(default)]
+    export_max_command_chars: usize,
+    // Show the cgroup/container column (reads /proc/<pid>/cgroup per process)
+    #[serde(default)]
+    show_cgroup: bool,
+    // Interfaces left out of network rates and the 'n' breakdown (e.g. add "docker0")
+    #[serde(default = "default_network_skip_interfaces")]
+    network_skip_interfaces: Vec<String>,
+    // Metrics on the second header line, in this order; see HEADER_FIELDS for the names
+    #[serde(default = "default_header_fields")]
+    header_fields: Vec<String>,
+    // List tmpfs/devtmpfs and similar memory-backed mounts in the 'd' disk overlay
+    #[serde(default)]
+    disk_show_pseudo: bool,
+    // Order per-core bars by physical core and label hyperthreads 0a/0b, 1a/1b, ...
+    #[serde(default)]
+    cpu_group_siblings: bool,
+    // Quiet processes (below both thresholds): "dim", "hide" or unset to show normally
+    #[serde(default)]
+    quiet_mode: Option<String>,
+    #[serde(default = "default_quiet_cpu_percent")]
+    quiet_cpu_percent: f32,
+    #[serde(default = "default_quiet_mem_percent")]
+    quiet_mem_percent: f32,
+    // No automatic refresh at all: data only updates when 'r' is pressed (toggle with 'M')
+    #[serde(default)]
+    manual_refresh: bool,
+    // Chart point marker: "dot", "braille", "block", "halfblock" or "bar"
+    // (unset = braille on UTF-8 terminals, dot elsewhere)
+    #[serde(default)]
+    chart_marker: Option<String>,
+    // "line" connects samples, "scatter" plots them as points (unset = line where Braille works)
+    #[serde(default)]
+    chart_style: Option<String>,
+    // "performance", "balanced" or "battery": sets ui_rate_ms, data_rate_ms, history_len and
+    // cpu_samples together (cycle with 'P'); unset = use those fields as written
+    #[serde(default)]
+    refresh_profile: Option<String>,
+    // F6 keeps the selected process selected (by PID) instead of the same row index
+    #[serde(default = "default_keep_selection_on_sort")]
+    keep_selection_on_sort: bool,
+    // F6 moves the cursor (and so the scroll) to the new first row; overrides
+    // keep_selection_on_sort. Both off = stay on the same row index
+    #[serde(default)]
+    sort_jump_to_top: bool,
+    // Horizontal reference line on the CPU and memory charts, in percent (0 = off)
+    #[serde(default = "default_chart_redline_percent")]
+    chart_redline_percent: f64,
+    // Dashboards: animation after screensaver_after_secs without input, until a key is pressed
+    #[serde(default)]
+    screensaver: bool,
+    #[serde(default = "default_screensaver_after_secs")]
+    screensaver_after_secs: u64,
+    // Fourth chart with the process count over time (climbing = leak or fork bomb)
+    #[serde(default)]
+    show_process_chart: bool,
+    // Swap usage as a second line on the memory chart
+    #[serde(default)]
+    chart_swap: bool,
+    // Themes Tab rotates through, in order (e.g. ["stellar", "matrix"] to skip dark)
+    #[serde(default = "default_theme_cycle")]
+    theme_cycle: Vec<String>,
+    // F9 asks before sending SIGTERM (the escalation to SIGKILL is never asked twice);
+    // false keeps the instant SIGTERM of older releases
+    #[serde(default = "default_confirm_kill")]
+    confirm_kill: bool,
+    // K sends SIGKILL to the selected process at once, with no dialog (off = K is inert)
+    #[serde(default)]
+    quick_kill: bool,
+    // Decimal places on CPU/memory/swap percentage labels (0-2)
+    #[serde(default = "default_percent_decimals")]
+    percent_decimals: usize,
+}
+
+fn default_screensaver_after_secs() -> u64 {
+    600
+}
+
+fn default_chart_redline_percent() -> f64 {
+    80.0
+}
+
+fn default_keep_selection_on_sort() -> bool {
+    true
+}
+
+fn default_theme_cycle() -> Vec<String> {
+    vec![
+        "dark".to_string(),
+        "stellar".to_string(),
+        "matrix".to_string(),
+    ]
+}
+
+fn default_network_skip_interfaces() -> Vec<String> {
+    vec!["lo".to_string()]
+}
+
+// Names accepted in header_fields; anything else is skipped (and reported once at startup)
+const HEADER_FIELDS: &[&str] = &["tasks", "load", "uptime", "fds", "net", "temp"];
+
+fn default_header_fields() -> Vec<String> {
+    ["tasks", "load", "uptime"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn default_confirm_kill() -> bool {
+    true
+}
+
+fn default_percent_decimals() -> usize {
+    1
+}
+
+fn default_quiet_cpu_percent() -> f32 {
+    0.5
+}
+
+fn default_quiet_mem_percent() -> f32 {
+    1.0
+}
+
+fn default_day_theme() -> String {
+    "stellar".to_string()
+}
+
+fn default_night_theme() -> String {
+    "matrix".to_string()
+}
+
+fn default_day_start_hour() -> u32 {
+    7
+}
+
+fn default_night_start_hour() -> u32 {
+    19
+}
+
+fn default_insights_top_n() -> usize {
+    5
+}
+
+fn default_cpu_samples() -> usize {
+    1
+}
+
+fn default_history_len() -> usize {
+    30
+}
+
+fn default_warn_percent() -> f64 {
+    75.0
+}
+
+fn default_critical_percent() -> f64 {
+    90.0
+}
+
+fn default_idle_timeout_secs() -> u64 {
+    300
+}
+
+fn default_idle_rate_ms() -> u64 {
+    10_000
+}
+
+fn default_ui_rate_ms() -> u64 {
+    500
+}
+
+fn default_data_rate_ms() -> u64 {
+    5000
+}
+
+fn default_max_rows() -> usize {
+    15
+}
+
+fn default_show_charts() -> bool {
+    true
+}
+
+fn default_kill_wait_ms() -> u64 {
+    3000
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
-            ui_rate_ms: 500,
-            data_rate_ms: 5000,
-            max_rows: 15,
-            show_charts: true,
+            config_version: CONFIG_VERSION,
+            ui_rate_ms: default_ui_rate_ms(),
+            data_rate_ms: default_data_rate_ms(),
+            process_rate_ms: None,
+            max_rows: default_max_rows(),
+            show_charts: default_show_charts(),
             theme: None,
             sort: None,
+            sort_reversed: false,
+            match_mode: None,
+            kill_wait_ms: default_kill_wait_ms(),
+            use_emoji: None,
+            startup_overlay: None,
+            startup_commands: Vec::new(),
+            idle_timeout_secs: default_idle_timeout_secs(),
+            idle_rate_ms: default_idle_rate_ms(),
+            warn_percent: default_warn_percent(),
+            critical_percent: default_critical_percent(),
+            cpu_samples: default_cpu_samples(),
+            history_len: default_history_len(),
+            insights_top_n: default_insights_top_n(),
+            renice_confirm_below: 0,
+            show_parent_name: false,
+            auto_theme: false,
+            day_theme: default_day_theme(),
+            night_theme: default_night_theme(),
+            day_start_hour: default_day_start_hour(),
+            night_start_hour: default_night_start_hour(),
+            export_compress: false,
+            export_max_command_chars: 0,
+            show_cgroup: false,
+            network_skip_interfaces: default_network_skip_interfaces(),
+            header_fields: default_header_fields(),
+            disk_show_pseudo: false,
+            cpu_group_siblings: false,
+            quiet_mode: None,
+            quiet_cpu_percent: default_quiet_cpu_percent(),
+            quiet_mem_percent: default_quiet_mem_percent(),
+            manual_refresh: false,
+            chart_marker: None,
+            chart_style: None,
+            refresh_profile: None,
+            keep_selection_on_sort: default_keep_selection_on_sort(),
+            sort_jump_to_top: false,
+            chart_redline_percent: default_chart_redline_percent(),
+            screensaver: false,
+            screensaver_after_secs: default_screensaver_after_secs(),
+            show_process_chart: false,
+            chart_swap: false,
+            theme_cycle: default_theme_cycle(),
+            confirm_kill: default_confirm_kill(),
+            quick_kill: false,
+            percent_decimals: default_percent_decimals(),
         }
     }
 }
@@ -223,6 +1257,19 @@ enum ConfigSource {
     Xdg,
 }
 
+// Emoji and Braille need a UTF-8 locale and a terminal with font fallback; the Linux VT has neither
+fn detect_unicode_support() -> bool {
+    if env::var("TERM").is_ok_and(|t| t == "linux" || t == "dumb") {
+        return false;
+    }
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|k| env::var(k).ok().filter(|v| !v.is_empty()))
+        .unwrap_or_default()
+        .to_lowercase();
+    locale.contains("utf-8") || locale.contains("utf8")
+}
+
 fn config_source_label(src: ConfigSource) -> &'static str {
     match src {
         ConfigSource::Env => "Env",
@@ -295,6 +1342,32 @@ fn discover_config_candidates() -> Vec<(PathBuf, ConfigSource)> {
     out
 }
 
+// How a Setup candidate relates to the config actually in use
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum CandidateState {
+    Missing,
+    Invalid,
+    SameAsActive,
+    DiffersFromActive,
+}
+
+fn candidate_state(path: &Path, active: &AppConfig) -> CandidateState {
+    let Ok(content) = fs::read_to_string(path) else {
+        return CandidateState::Missing;
+    };
+    match parse_config(&content) {
+        Ok((cfg, _, _)) => {
+            // Compare normalized forms so formatting and comments don't count as differences
+            if toml::to_string(&cfg).ok() == toml::to_string(active).ok() {
+                CandidateState::SameAsActive
+            } else {
+                CandidateState::DiffersFromActive
+            }
+        }
+        Err(_) => CandidateState::Invalid,
+    }
+}
+
 fn resolve_config_path() -> (PathBuf, ConfigSource) {
     // Highest priority: explicit path via env
     if let Ok(p) = env::var("LYVOXA_CONFIG") {
@@ -358,21 +1431,150 @@ fn resolve_config_path() -> (PathBuf, ConfigSource) {
     (p, ConfigSource::Xdg)
 }
 
-fn save_config_file_at(path: &Path, cfg: &AppConfig) -> io::Result<()> {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
+// Write an export file, gzip-compressed when requested (long unattended runs add up fast)
+fn write_export(path: &Path, data: &[u8], compress: bool) -> io::Result<()> {
+    if !compress {
+        return fs::write(path, data);
     }
-    let data = toml::to_string_pretty(cfg).unwrap_or_else(|_| String::new());
-    fs::write(path, data)
+    use flate2::{Compression, write::GzEncoder};
+    use std::io::Write;
+
+    let mut encoder = GzEncoder::new(fs::File::create(path)?, Compression::default());
+    encoder.write_all(data)?;
+    encoder.finish()?;
+    Ok(())
 }
 
-struct App {
-    monitor: SystemMonitor,
-    should_quit: bool,
-    cpu_history: VecDeque<f64>,
+// Start argv[0] with the remaining args in its own process group, stdio discarded,
+// so it neither draws over the TUI nor dies with it
+fn spawn_detached(args: &[String], cwd: Option<&Path>) -> io::Result<u32> {
+    use std::os::unix::process::CommandExt;
+    use std::process::{Command, Stdio};
+
+    let Some((program, rest)) = args.split_first() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "empty command line",
+        ));
+    };
+    let mut cmd = Command::new(program);
+    cmd.args(rest)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .process_group(0);
+    if let Some(dir) = cwd {
+        cmd.current_dir(dir);
+    }
+    let mut child = cmd.spawn()?;
+    let pid = child.id();
+    // Reap it when it eventually exits so it doesn't linger as a zombie
+    std::thread::spawn(move || child.wait());
+    Ok(pid)
+}
+
+// Tree order for an already sorted view: children follow their parent (siblings keep the
+// sort order), processes whose parent isn't visible become roots, and collapsed subtrees
+// are skipped. Returns (process index, prefix to draw before the command).
+fn tree_order(
+    procs: &[monitor::ProcessInfo],
+    view: &[usize],
+    collapsed: &HashSet<u32>,
+    ascii: bool,
+) -> Vec<(usize, String)> {
+    let (branch, last, pipe) = if ascii {
+        ("|- ", "`- ", "|  ")
+    } else {
+        ("├─ ", "└─ ", "│  ")
+    };
+    let visible: HashSet<u32> = view.iter().map(|&i| procs[i].pid).collect();
+    let mut children: HashMap<u32, Vec<usize>> = HashMap::new();
+    let mut roots = Vec::new();
+    for &i in view {
+        let p = &procs[i];
+        match p.ppid {
+            Some(ppid) if ppid != p.pid && visible.contains(&ppid) => {
+                children.entry(ppid).or_default().push(i)
+            }
+            _ => roots.push(i),
+        }
+    }
+
+    let mut out = Vec::with_capacity(view.len());
+    let mut seen = HashSet::new();
+    // (process index, indent inherited from ancestors, connector for this node)
+    let mut stack: Vec<(usize, String, &str)> = roots
+        .into_iter()
+        .rev()
+        .map(|i| (i, String::new(), ""))
+        .collect();
+    while let Some((i, indent, connector)) = stack.pop() {
+        let pid = procs[i].pid;
+        // PID reuse can make ppid links loop; never visit a process twice
+        if !seen.insert(pid) {
+            continue;
+        }
+        let kids = children.get(&pid).map(Vec::as_slice).unwrap_or(&[]);
+        let fold = if !kids.is_empty() && collapsed.contains(&pid) {
+            "[+] "
+        } else {
+            ""
+        };
+        out.push((i, format!("{}{}{}", indent, connector, fold)));
+        if !fold.is_empty() {
+            continue;
+        }
+        // Roots don't indent their children's continuation lines
+        let child_indent = match connector {
+            "" => String::new(),
+            c if c == last => format!("{}   ", indent),
+            _ => format!("{}{}", indent, pipe),
+        };
+        for (n, &child) in kids.iter().enumerate().rev() {
+            let connector = if n + 1 == kids.len() { last } else { branch };
+            stack.push((child, child_indent.clone(), connector));
+        }
+    }
+    out
+}
+
+// Search and filter terms are AND-ed; numeric terms also hit the PID itself or every child
+// of that PPID
+fn matches_terms(p: &monitor::ProcessInfo, terms: &[(&str, TermMatcher)]) -> bool {
+    terms.iter().all(|(term, matcher)| {
+        matcher.is_match(&p.command)
+            || matcher.is_match(&p.user)
+            || term
+                .parse::<u32>()
+                .is_ok_and(|n| p.pid == n || p.ppid == Some(n))
+    })
+}
+
+// Below the activity thresholds on both CPU and memory
+fn is_quiet(p: &monitor::ProcessInfo, cfg: &AppConfig) -> bool {
+    p.cpu_usage < cfg.quiet_cpu_percent && p.mem_percent < cfg.quiet_mem_percent
+}
+
+fn save_config_file_at(path: &Path, cfg: &AppConfig) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let data = toml::to_string_pretty(cfg).unwrap_or_else(|_| String::new());
+    fs::write(path, data)
+}
+
+struct App {
+    monitor: SystemMonitor,
+    should_quit: bool,
+    cpu_history: VecDeque<f64>,
     memory_history: VecDeque<f64>,
+    // Swap used %, in step with memory_history (0 on systems without swap)
+    swap_history: VecDeque<f64>,
     net_rx_history: VecDeque<f64>,
     net_tx_history: VecDeque<f64>,
+    process_count_history: VecDeque<f64>,
+    // Capture time of each history sample (same index as the histories above)
+    history_times: VecDeque<Instant>,
     last_update: Instant,
     processes: Vec<monitor::ProcessInfo>,
     process_view: Vec<usize>,
@@ -381,7 +1583,9 @@ struct App {
     theme: Theme,
     overlay: Overlay,
     input_buffer: String,
-    filter: String,
+    // Active filter terms, AND-ed together (F4 adds one, shown as chips in the header)
+    filters: Vec<String>,
+    filter_selected: usize,
     search: String,
     sort_key: SortKey,
     selected: usize,
@@ -390,62 +1594,226 @@ struct App {
     config_path: PathBuf,
     config_source: ConfigSource,
     setup_sources: Vec<(PathBuf, ConfigSource)>,
+    // Parallel to setup_sources, computed on refresh
+    setup_states: Vec<CandidateState>,
     setup_selected: usize,
+    // Config editor: the config as it was when opened (restored on cancel) and the cursor
+    editor_original: Option<AppConfig>,
+    editor_selected: usize,
+    editor_editing: bool,
+    pending_kill: Option<PendingKill>,
+    use_emoji: bool,
+    // Terminal can render Braille, so unset chart_marker/chart_style default to Braille lines
+    braille_charts: bool,
+    last_input: Instant,
+    idle: bool,
+    detail_pid: Option<u32>,
+    // Read once when the detail view opens; smaps is too costly to parse every frame
+    detail_mem: Option<Result<monitor::MemoryMapSummary, String>>,
+    detail_sched: Option<Result<monitor::SchedPolicy, String>>,
+    // Re-read on every process refresh while the detail view is open: a hang shows up
+    // as the same function over and over
+    detail_wchan: Option<String>,
+    // 't' tree view: children under parents; Space folds the selected subtree
+    tree_mode: bool,
+    collapsed: HashSet<u32>,
+    // Parallel to process_view in tree mode: box-drawing prefix for the command column
+    tree_prefixes: Vec<String>,
+    // Signal picker: highlighted entry and the PID captured when it opened
+    signal_selected: usize,
+    signal_pid: Option<u32>,
+    // Read when the system info overlay opens
+    system_info: Option<(monitor::SystemInfo, monitor::BootInfo)>,
+    // Refreshed with the fast metrics while the network overlay is open
+    iface_rates: Vec<(String, f64, f64)>,
+    // Not root: lowering nice and signalling other users' processes will hit EPERM
+    unprivileged: bool,
+    // PIDs that appeared in the latest process refresh (gutter '+')
+    new_pids: HashSet<u32>,
+    event_log: VecDeque<String>,
+    // Recent (cpu%, mem%) per PID and how far the latest sample strays from that baseline
+    proc_history: HashMap<u32, VecDeque<(f32, f32)>>,
+    deviation: HashMap<u32, f32>,
+    pending_action: Option<PendingAction>,
+    pending_restart: Option<PendingRestart>,
+    zen: bool,
+    // Chart inspection cursor, in samples back from the newest (None = cursor hidden)
+    chart_cursor: Option<usize>,
+    // Session-only env vars that replaced file values (shown in header and F2 setup)
+    env_overrides: Vec<EnvOverride>,
+    plugins: plugin::PluginManager,
+    // Latest monitoring-plugin metrics by name (sorted for a stable panel)
+    plugin_metrics: BTreeMap<String, f64>,
+    plugin_metrics_pending: bool,
+    export_targets: Vec<ExportTarget>,
+    // Progress/outcome of the current or last export, shown in the Export overlay
+    export_state: ExportState,
+    // Write queued by start_export, picked up by run_app
+    export_job: Option<ExportJob>,
+    // Latest (plugin-processed) metrics sample, reused by plugin exporters
+    last_snapshot: plugin::SystemSnapshot,
+    // --anonymize: exports drop the hostname, pseudonymize users and strip arguments
+    anonymize_exports: bool,
+    // Screensaver animation state; Some while it is showing
+    screensaver: Option<screensaver::Rain>,
+    // Process table rows area and scroll offset from the last frame, for mapping mouse clicks
+    // (ui() only gets &App, hence the Cell)
+    process_rows_area: std::cell::Cell<(Rect, usize)>,
+    // Tick rates changed (profile, config edit); run_app rebuilds its intervals
+    rates_changed: bool,
+    // Swap-in/out pages per second over the last data refresh
+    swap_rates: (f64, f64),
+    // CPU package temperature in °C, read with the slow data; None without a sensor
+    cpu_temp: Option<f32>,
+    // (open file handles, limit) for the "fds" header field
+    open_files: Option<(u64, u64)>,
+    // Process highlighted when fresh data replaced `processes`; the rebuilt view moves the
+    // cursor to its new row so refresh re-sorts don't shift F7/F8/F9 onto another process
+    pinned_pid: Option<u32>,
+    // Wall-clock time of the last process/system refresh (exports report it when frozen)
+    data_refreshed_at: Option<chrono::DateTime<chrono::Local>>,
+    export_selected: usize,
+    // Last day/night phase applied by auto_theme (None until the first check)
+    auto_theme_is_day: Option<bool>,
+}
+
+// A signalled process we are still watching for exit
+#[derive(Copy, Clone, Debug)]
+struct PendingKill {
+    pid: u32,
+    sent_at: Instant,
+    escalated: bool,
+    survived: bool,
+}
+
+// A config field whose value came from a LYVOXA_* environment variable
+#[derive(Clone, Debug)]
+struct EnvOverride {
+    var: &'static str,
+    field: &'static str,
+    value: String,
 }
 
 impl App {
     fn new() -> App {
         // Start with defaults, overlay file config, then env overrides into an effective config.
-        let (file_cfg, existed, cfg_path, cfg_src) = load_config_file_with_flag();
+        let (file_cfg, existed, cfg_path, cfg_src, cfg_warning) = load_config_file_with_flag();
         let mut config = file_cfg.clone();
+        // Remember which fields env replaced so the UI can say where a value came from
+        let mut env_overrides = Vec::new();
         if let Ok(v) = env::var("LYVOXA_UI_MS")
             && let Ok(ms) = v.parse::<u64>()
         {
             config.ui_rate_ms = ms;
+            env_overrides.push(EnvOverride {
+                var: "LYVOXA_UI_MS",
+                field: "ui_rate_ms",
+                value: v,
+            });
         }
         if let Ok(v) = env::var("LYVOXA_DATA_MS")
             && let Ok(ms) = v.parse::<u64>()
         {
             config.data_rate_ms = ms;
+            env_overrides.push(EnvOverride {
+                var: "LYVOXA_DATA_MS",
+                field: "data_rate_ms",
+                value: v,
+            });
         }
         if let Ok(v) = env::var("LYVOXA_ROWS")
             && let Ok(n) = v.parse::<usize>()
         {
             config.max_rows = n;
+            env_overrides.push(EnvOverride {
+                var: "LYVOXA_ROWS",
+                field: "max_rows",
+                value: v,
+            });
         }
         if let Ok(v) = env::var("LYVOXA_SHOW_CHARTS") {
-            let v = v.to_lowercase();
-            config.show_charts = !(v == "0" || v == "false");
+            let lower = v.to_lowercase();
+            config.show_charts = !(lower == "0" || lower == "false");
+            env_overrides.push(EnvOverride {
+                var: "LYVOXA_SHOW_CHARTS",
+                field: "show_charts",
+                value: v,
+            });
         }
 
-        // Map config theme/sort to runtime enums with robust defaults
-        let theme_kind = match config.theme.as_deref() {
-            Some("dark") => ThemeKind::Dark,
-            Some("matrix") => ThemeKind::Matrix,
-            Some("stellar") => ThemeKind::Stellar,
-            _ => ThemeKind::Stellar,
-        };
-        let sort_key = match config.sort.as_deref() {
-            Some("mem") => SortKey::Mem,
-            Some("pid") => SortKey::Pid,
-            Some("user") => SortKey::User,
-            Some("command") => SortKey::Command,
-            Some("cpu") => SortKey::Cpu,
-            _ => SortKey::Cpu,
-        };
-
         // Ensure config file exists on first run (write only file defaults, not env overrides)
         if !existed {
             let _ = save_config_file_at(&cfg_path, &file_cfg);
         }
 
+        App::with_config(config, cfg_path, cfg_src, cfg_warning, env_overrides)
+    }
+
+    /// Build the app from an already-resolved config without touching the config file.
+    /// Used by `new()` and by the render tests, which need a known config.
+    fn with_config(
+        config: AppConfig,
+        cfg_path: PathBuf,
+        cfg_src: ConfigSource,
+        mut cfg_warning: Option<String>,
+        env_overrides: Vec<EnvOverride>,
+    ) -> App {
+        // Map config theme/sort to runtime enums with robust defaults
+        let theme_kind = config
+            .theme
+            .as_deref()
+            .and_then(theme_kind_from_str)
+            .unwrap_or(ThemeKind::Stellar);
+        let sort_key = config
+            .sort
+            .as_deref()
+            .and_then(sort_key_from_str)
+            .unwrap_or(SortKey::Cpu);
+
+        // Plugin settings and security policy live next to the main config
+        let mut plugins = plugin::PluginManager::new();
+        let plugin_cfg = cfg_path.with_file_name("plugins.toml");
+        if plugin_cfg.exists()
+            && let Err(e) = plugins.load_config(&plugin_cfg.to_string_lossy())
+        {
+            cfg_warning = Some(format!("Plugins: {}", e));
+        }
+        if let Err(e) = plugins
+            .register_export_plugin(Box::new(plugin::HtmlExporterPlugin::new()))
+            .and_then(|_| plugins.initialize_all())
+        {
+            cfg_warning = Some(format!("Plugins: {}", e));
+        }
+
+        let unknown_fields: Vec<&str> = config
+            .header_fields
+            .iter()
+            .map(String::as_str)
+            .filter(|f| !HEADER_FIELDS.contains(f))
+            .collect();
+        if !unknown_fields.is_empty() && cfg_warning.is_none() {
+            cfg_warning = Some(format!(
+                "Unknown header_fields ignored: {} (known: {})",
+                unknown_fields.join(", "),
+                HEADER_FIELDS.join(", ")
+            ));
+        }
+
+        let mut monitor = SystemMonitor::new();
+        monitor.set_cpu_samples(config.cpu_samples);
+        monitor.set_collect_cgroups(config.show_cgroup);
+        monitor.set_skipped_interfaces(&config.network_skip_interfaces);
+
         App {
-            monitor: SystemMonitor::new(),
+            monitor,
             should_quit: false,
-            cpu_history: VecDeque::with_capacity(30),
-            memory_history: VecDeque::with_capacity(30),
-            net_rx_history: VecDeque::with_capacity(30),
-            net_tx_history: VecDeque::with_capacity(30),
+            cpu_history: VecDeque::with_capacity(config.history_len),
+            memory_history: VecDeque::with_capacity(config.history_len),
+            swap_history: VecDeque::with_capacity(config.history_len),
+            net_rx_history: VecDeque::with_capacity(config.history_len),
+            net_tx_history: VecDeque::with_capacity(config.history_len),
+            process_count_history: VecDeque::with_capacity(config.history_len),
+            history_times: VecDeque::with_capacity(config.history_len),
             last_update: Instant::now(),
             processes: Vec::new(),
             process_view: Vec::new(),
@@ -454,19 +1822,83 @@ impl App {
             theme: Theme::palette(theme_kind),
             overlay: Overlay::None,
             input_buffer: String::new(),
-            filter: String::new(),
+            filters: Vec::new(),
+            filter_selected: 0,
             search: String::new(),
             sort_key,
             selected: 0,
-            status_message: None,
-            config,
+            status_message: cfg_warning,
             config_path: cfg_path,
             config_source: cfg_src,
             setup_sources: Vec::new(),
+            setup_states: Vec::new(),
             setup_selected: 0,
+            editor_original: None,
+            editor_selected: 0,
+            editor_editing: false,
+            pending_kill: None,
+            last_input: Instant::now(),
+            idle: false,
+            detail_pid: None,
+            detail_mem: None,
+            detail_sched: None,
+            detail_wchan: None,
+            system_info: None,
+            iface_rates: Vec::new(),
+            unprivileged: unsafe { libc::geteuid() } != 0,
+            tree_mode: false,
+            collapsed: HashSet::new(),
+            tree_prefixes: Vec::new(),
+            signal_selected: 0,
+            signal_pid: None,
+            new_pids: HashSet::new(),
+            event_log: VecDeque::with_capacity(200),
+            proc_history: HashMap::new(),
+            deviation: HashMap::new(),
+            pending_action: None,
+            pending_restart: None,
+            zen: false,
+            chart_cursor: None,
+            env_overrides,
+            plugins,
+            plugin_metrics: BTreeMap::new(),
+            plugin_metrics_pending: false,
+            export_targets: Vec::new(),
+            export_state: ExportState::Idle,
+            export_job: None,
+            last_snapshot: plugin::SystemSnapshot::default(),
+            anonymize_exports: false,
+            swap_rates: (0.0, 0.0),
+            cpu_temp: None,
+            open_files: None,
+            pinned_pid: None,
+            data_refreshed_at: None,
+            screensaver: None,
+            process_rows_area: std::cell::Cell::new((Rect::default(), 0)),
+            rates_changed: false,
+            export_selected: 0,
+            auto_theme_is_day: None,
+            use_emoji: config.use_emoji.unwrap_or_else(detect_unicode_support),
+            braille_charts: detect_unicode_support(),
+            config,
         }
     }
 
+    fn severity_color(&self, percent: f64, normal: Color) -> Color {
+        theme::get_severity_color(
+            &self.theme,
+            percent,
+            self.config.warn_percent,
+            self.config.critical_percent,
+            normal,
+        )
+    }
+
+    // Pick an emoji or its ASCII stand-in depending on terminal support
+    fn icon(&self, emoji: &'static str, ascii: &'static str) -> &'static str {
+        if self.use_emoji { emoji } else { ascii }
+    }
+
     fn rebuild_process_view(&mut self) {
         if !self.process_view_dirty {
             return;
@@ -475,32 +1907,25 @@ impl App {
         self.process_view.clear();
         self.process_view.extend(0..self.processes.len());
 
-        let filter_term = self.filter.trim();
-        let search_term = self.search.trim();
-
-        if !filter_term.is_empty() || !search_term.is_empty() {
-            let filter_lc = filter_term.to_lowercase();
-            let search_lc = search_term.to_lowercase();
-
-            self.process_view.retain(|&idx| {
-                let p = &self.processes[idx];
-                let cmd = p.command.to_lowercase();
-                let usr = p.user.to_lowercase();
-
-                let filter_ok = if filter_term.is_empty() {
-                    true
-                } else {
-                    cmd.contains(&filter_lc) || usr.contains(&filter_lc)
-                };
+        // Search and filter chips are AND-ed alike
+        let mode = self.match_mode();
+        let terms: Vec<(&str, TermMatcher)> = self
+            .filters
+            .iter()
+            .chain(std::iter::once(&self.search))
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+            .map(|t| (t, TermMatcher::new_or_literal(t, mode)))
+            .collect();
 
-                let search_ok = if search_term.is_empty() {
-                    true
-                } else {
-                    cmd.contains(&search_lc) || usr.contains(&search_lc)
-                };
+        if !terms.is_empty() {
+            self.process_view
+                .retain(|&idx| matches_terms(&self.processes[idx], &terms));
+        }
 
-                filter_ok && search_ok
-            });
+        if self.config.quiet_mode.as_deref() == Some("hide") {
+            self.process_view
+                .retain(|&idx| !is_quiet(&self.processes[idx], &self.config));
         }
 
         match self.sort_key {
@@ -517,10 +1942,37 @@ impl App {
                         .cmp(&self.processes[a].mem_bytes)
                 });
             }
+            // Address-space size: large mappings that may never be touched
+            SortKey::Virt => {
+                self.process_view
+                    .sort_by(|&a, &b| self.processes[b].virt.cmp(&self.processes[a].virt));
+            }
+            // Shared pages (libraries, shm) counted in MEM but not owned by the process
+            SortKey::Shr => {
+                self.process_view
+                    .sort_by(|&a, &b| self.processes[b].shr.cmp(&self.processes[a].shr));
+            }
+            SortKey::DiskRead => self.process_view.sort_by(|&a, &b| {
+                self.processes[b]
+                    .disk_read_bytes_per_sec
+                    .partial_cmp(&self.processes[a].disk_read_bytes_per_sec)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            }),
+            SortKey::DiskWrite => self.process_view.sort_by(|&a, &b| {
+                self.processes[b]
+                    .disk_write_bytes_per_sec
+                    .partial_cmp(&self.processes[a].disk_write_bytes_per_sec)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            }),
             SortKey::Pid => {
                 self.process_view
                     .sort_by(|&a, &b| self.processes[a].pid.cmp(&self.processes[b].pid));
             }
+            SortKey::Ppid => {
+                // Children of the same parent end up adjacent, ordered by PID
+                self.process_view
+                    .sort_by_key(|&i| (self.processes[i].ppid.unwrap_or(0), self.processes[i].pid));
+            }
             SortKey::User => {
                 self.process_view
                     .sort_by(|&a, &b| self.processes[a].user.cmp(&self.processes[b].user));
@@ -529,15 +1981,68 @@ impl App {
                 self.process_view
                     .sort_by(|&a, &b| self.processes[a].command.cmp(&self.processes[b].command));
             }
+            SortKey::Deviation => {
+                let score = |idx: usize| {
+                    self.deviation
+                        .get(&self.processes[idx].pid)
+                        .copied()
+                        .unwrap_or(0.0)
+                };
+                self.process_view.sort_by(|&a, &b| {
+                    score(b)
+                        .partial_cmp(&score(a))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+            }
+        }
+
+        // Before the tree pass, so it flips sibling order instead of breaking the hierarchy
+        if self.config.sort_reversed {
+            self.process_view.reverse();
+        }
+
+        self.tree_prefixes.clear();
+        if self.tree_mode {
+            let ordered = tree_order(
+                &self.processes,
+                &self.process_view,
+                &self.collapsed,
+                !self.braille_charts,
+            );
+            self.process_view.clear();
+            for (idx, prefix) in ordered {
+                self.process_view.push(idx);
+                self.tree_prefixes.push(prefix);
+            }
         }
 
         if self.process_view.len() > self.config.max_rows {
             self.process_view.truncate(self.config.max_rows);
+            self.tree_prefixes.truncate(self.config.max_rows);
         }
 
+        if let Some(pid) = self.pinned_pid.take()
+            && let Some(idx) = self
+                .process_view
+                .iter()
+                .position(|&pidx| self.processes[pidx].pid == pid)
+        {
+            self.selected = idx;
+        }
+        // Gone (or cut off by max_rows): stay on the nearest row that still exists
+        self.selected = self.selected.min(self.process_view.len().saturating_sub(1));
+
         self.process_view_dirty = false;
     }
 
+    // PID shown at `row` of the current view (indices are only valid until `processes` changes)
+    fn pid_at(&self, row: usize) -> Option<u32> {
+        self.process_view
+            .get(row)
+            .and_then(|&pidx| self.processes.get(pidx))
+            .map(|p| p.pid)
+    }
+
     fn refresh_config_candidates(&mut self) {
         self.setup_sources = discover_config_candidates();
         // Ensure current config is at top if not present
@@ -549,6 +2054,16 @@ impl App {
             self.setup_sources
                 .insert(0, (self.config_path.clone(), self.config_source));
         }
+        // Compare against the active file as written, not the session's env overrides
+        let active = fs::read_to_string(&self.config_path)
+            .ok()
+            .and_then(|s| parse_config(&s).ok())
+            .map_or_else(|| self.config.clone(), |(cfg, _, _)| cfg);
+        self.setup_states = self
+            .setup_sources
+            .iter()
+            .map(|(p, _)| candidate_state(p, &active))
+            .collect();
         self.setup_selected = 0;
     }
 
@@ -561,7 +2076,8 @@ impl App {
         if path.exists() {
             match fs::read_to_string(&path)
                 .ok()
-                .and_then(|s| toml::from_str::<AppConfig>(&s).ok())
+                .and_then(|s| parse_config(&s).ok())
+                .map(|(cfg, _, _)| cfg)
             {
                 Some(cfg) => {
                     self.config = cfg;
@@ -577,51 +2093,149 @@ impl App {
                 }
             }
         } else {
-            // Create new file from current in-memory config
-            if let Err(e) = save_config_file_at(&path, &self.config) {
+            // Never create files silently (think /etc or a repo checkout): preview and confirm first
+            self.pending_action = Some(PendingAction::CreateConfig { path, source });
+            self.overlay = Overlay::Confirm;
+        }
+    }
+
+    // Create a new config file from the current in-memory config and switch to it
+    fn create_config_at(&mut self, path: PathBuf, source: ConfigSource) {
+        if let Err(e) = save_config_file_at(&path, &self.config) {
+            self.status_message = Some(format!(
+                "Failed to create config: {} ({})",
+                path.display(),
+                e
+            ));
+            return;
+        }
+        self.config_path = path;
+        self.config_source = source;
+        self.apply_config_runtime();
+        self.status_message = Some(format!("Config created: {}", self.config_path.display()));
+    }
+
+    // Push config to runtime state that isn't read live: theme, sort, monitor options, tick rates
+    fn apply_config_runtime(&mut self) {
+        self.theme_kind = self
+            .config
+            .theme
+            .as_deref()
+            .and_then(theme_kind_from_str)
+            .unwrap_or(ThemeKind::Stellar);
+        self.theme = Theme::palette(self.theme_kind);
+        self.sort_key = self
+            .config
+            .sort
+            .as_deref()
+            .and_then(sort_key_from_str)
+            .unwrap_or(SortKey::Cpu);
+        self.monitor.set_cpu_samples(self.config.cpu_samples);
+        self.monitor.set_collect_cgroups(self.config.show_cgroup);
+        self.monitor
+            .set_skipped_interfaces(&self.config.network_skip_interfaces);
+        self.rates_changed = true;
+        self.process_view_dirty = true;
+    }
+
+    // 'P': performance -> balanced -> battery -> performance
+    fn cycle_refresh_profile(&mut self) {
+        let next = match self.config.refresh_profile.as_deref() {
+            Some(cur) => REFRESH_PROFILES
+                .iter()
+                .position(|(n, ..)| *n == cur)
+                .map_or(0, |i| (i + 1) % REFRESH_PROFILES.len()),
+            None => 0,
+        };
+        let (name, ui_ms, data_ms, ..) = REFRESH_PROFILES[next];
+        apply_refresh_profile(&mut self.config, name);
+        self.apply_config_runtime();
+        let _ = save_config_file_at(&self.config_path, &self.config);
+        self.status_message = Some(format!(
+            "Refresh profile: {} (UI {}ms, data {}ms)",
+            name, ui_ms, data_ms
+        ));
+    }
+
+    fn open_config_editor(&mut self) {
+        self.editor_original = Some(self.config.clone());
+        self.editor_selected = 0;
+        self.editor_editing = false;
+        self.input_buffer.clear();
+        self.overlay = Overlay::ConfigEditor;
+    }
+
+    // Apply one field edit live; the editor stays open so the effect is visible behind it
+    fn edit_config_field(&mut self, input: &str) {
+        let key = CONFIG_EDITOR_FIELDS[self.editor_selected];
+        match set_config_field(&self.config, key, input) {
+            Ok(mut cfg) => {
+                // Picking a profile sets its rates; touching a rate by hand leaves the profile
+                if key == "refresh_profile" {
+                    if let Some(name) = cfg.refresh_profile.clone() {
+                        apply_refresh_profile(&mut cfg, &name);
+                    }
+                } else if matches!(
+                    key,
+                    "ui_rate_ms" | "data_rate_ms" | "history_len" | "cpu_samples"
+                ) {
+                    cfg.refresh_profile = None;
+                }
+                self.config = cfg;
+                self.apply_config_runtime();
                 self.status_message = Some(format!(
-                    "Failed to create config: {} ({})",
-                    path.display(),
-                    e
+                    "{} = {}",
+                    key,
+                    config_field_value(&self.config, key).unwrap_or_else(|| "(unset)".into())
                 ));
-                return;
             }
-            self.config_path = path;
-            self.config_source = source;
-            // Apply theme and sort from config to runtime
-            self.theme_kind = match self.config.theme.as_deref() {
-                Some("dark") => ThemeKind::Dark,
-                Some("matrix") => ThemeKind::Matrix,
-                _ => ThemeKind::Stellar,
-            };
-            self.theme = Theme::palette(self.theme_kind);
-            self.sort_key = match self.config.sort.as_deref() {
-                Some("mem") => SortKey::Mem,
-                Some("pid") => SortKey::Pid,
-                Some("user") => SortKey::User,
-                Some("command") => SortKey::Command,
-                _ => SortKey::Cpu,
-            };
-            self.process_view_dirty = true;
-            self.status_message = Some(format!("Config switched: {}", self.config_path.display()));
+            Err(e) => self.status_message = Some(format!("Invalid value, {}", e)),
+        }
+    }
+
+    fn close_config_editor(&mut self, save: bool) {
+        if save {
+            self.status_message =
+                Some(match save_config_file_at(&self.config_path, &self.config) {
+                    Ok(()) => format!("Config saved: {}", self.config_path.display()),
+                    Err(e) => format!("Failed to save config: {}", e),
+                });
+        } else if let Some(original) = self.editor_original.take() {
+            self.config = original;
+            self.apply_config_runtime();
+            self.status_message = Some("Config edits discarded".to_string());
         }
+        self.editor_original = None;
+        self.editor_editing = false;
+        self.input_buffer.clear();
+        self.overlay = Overlay::None;
     }
 
     fn cycle_theme(&mut self, next: bool) {
-        self.theme_kind = match (self.theme_kind, next) {
-            // Forward cycling: Dark → Stellar → Matrix → Dark
-            (ThemeKind::Dark, true) => ThemeKind::Stellar,
-            (ThemeKind::Stellar, true) => ThemeKind::Matrix,
-            (ThemeKind::Matrix, true) => ThemeKind::Dark,
-            // Backward cycling: Dark → Matrix → Stellar → Dark
-            (ThemeKind::Dark, false) => ThemeKind::Matrix,
-            (ThemeKind::Stellar, false) => ThemeKind::Dark,
-            (ThemeKind::Matrix, false) => ThemeKind::Stellar,
+        // Rotate through theme_cycle in its order (default Dark → Stellar → Matrix)
+        let mut cycle: Vec<ThemeKind> = self
+            .config
+            .theme_cycle
+            .iter()
+            .filter_map(|name| theme_kind_from_str(name))
+            .collect();
+        if cycle.is_empty() {
+            cycle = vec![ThemeKind::Dark, ThemeKind::Stellar, ThemeKind::Matrix];
+        }
+        self.theme_kind = match cycle.iter().position(|&k| k == self.theme_kind) {
+            Some(i) if next => cycle[(i + 1) % cycle.len()],
+            Some(i) => cycle[(i + cycle.len() - 1) % cycle.len()],
+            // Current theme isn't in the cycle: jump into it
+            None => cycle[0],
         };
         self.theme = Theme::palette(self.theme_kind);
 
         // Add smooth transition effect (status message)
-        self.status_message = Some(format!("🎨 Theme switched to: {:?}", self.theme_kind));
+        self.status_message = Some(format!(
+            "{} Theme switched to: {:?}",
+            self.icon("🎨", "[theme]"),
+            self.theme_kind
+        ));
 
         // Persist theme to config file
         self.config.theme = Some(match self.theme_kind {
@@ -632,12 +2246,54 @@ impl App {
         let _ = save_config_file_at(&self.config_path, &self.config);
     }
 
-    fn export_snapshot(&mut self) {
+    // Switch theme only when the day/night phase flips, so a manual Tab sticks until then
+    fn apply_auto_theme(&mut self) {
+        use chrono::Timelike;
+
+        if !self.config.auto_theme {
+            return;
+        }
+        let hour = chrono::Local::now().hour();
+        let (start, end) = (self.config.day_start_hour, self.config.night_start_hour);
+        let is_day = if start <= end {
+            hour >= start && hour < end
+        } else {
+            hour >= start || hour < end
+        };
+        if self.auto_theme_is_day == Some(is_day) {
+            return;
+        }
+        self.auto_theme_is_day = Some(is_day);
+        let name = if is_day {
+            &self.config.day_theme
+        } else {
+            &self.config.night_theme
+        };
+        if let Some(kind) = theme_kind_from_str(name)
+            && kind != self.theme_kind
+        {
+            self.theme_kind = kind;
+            self.theme = Theme::palette(kind);
+            self.status_message = Some(format!(
+                "{} Auto theme ({}): {:?}",
+                self.icon("🎨", "[theme]"),
+                if is_day { "day" } else { "night" },
+                kind
+            ));
+        }
+    }
+
+    // Build the JSON snapshot now; the returned job does the (possibly slow) write
+    fn export_snapshot(&mut self) -> ExportJob {
         use chrono::{DateTime, Local};
 
         let now: DateTime<Local> = Local::now();
         let timestamp = now.format("%Y%m%d_%H%M%S").to_string();
-        let filename = format!("lyvoxa_snapshot_{}.json", timestamp);
+        let filename = if self.config.export_compress {
+            format!("lyvoxa_snapshot_{}.json.gz", timestamp)
+        } else {
+            format!("lyvoxa_snapshot_{}.json", timestamp)
+        };
 
         // Collect current system data
         let cpu_usage = if let Some(&last_cpu) = self.cpu_history.back() {
@@ -658,57 +2314,182 @@ impl App {
             (0.0, 0.0)
         };
 
-        let top_processes = self.collect_processes(5); // Reduced from 10 to 5
-
-        let snapshot_data = format!(
-            r#"{{
-  "timestamp": "{}",
-  "version": "{}",
-  "theme": "{:?}",
-  "system_metrics": {{
-    "cpu_usage_percent": {:.2},
-    "memory_usage_percent": {:.2},
-    "network_rx_bytes_per_sec": {:.2},
-    "network_tx_bytes_per_sec": {:.2}
-  }},
-  "top_processes": [{}
-  ]
-}}"#,
-            now.format("%Y-%m-%d %H:%M:%S"),
-            VERSION,
-            self.theme_kind,
-            cpu_usage,
-            memory_usage,
-            net_rx,
-            net_tx,
-            top_processes
+        let top_processes = self.export_processes(5); // Reduced from 10 to 5
+        let staleness = self.data_staleness();
+
+        let snapshot_data = snapshot::Snapshot {
+            timestamp: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+            version: VERSION.to_string(),
+            theme: format!("{:?}", self.theme_kind),
+            system_metrics: snapshot::SnapshotMetrics {
+                cpu_usage_percent: cpu_usage,
+                memory_usage_percent: memory_usage,
+                network_rx_bytes_per_sec: net_rx,
+                network_tx_bytes_per_sec: net_tx,
+            },
+            top_processes: top_processes
                 .iter()
-                .map(|p| format!(
-                    r#"
-    {{
-      "pid": {},
-      "user": "{}",
-      "command": "{}",
-      "cpu_percent": {:.2},
-      "memory_bytes": {}
-    }}"#,
-                    p.pid, p.user, p.command, p.cpu_usage, p.mem_bytes
-                ))
-                .collect::<Vec<_>>()
-                .join(",")
-        );
+                .map(|p| snapshot::SnapshotProcess {
+                    pid: p.pid,
+                    user: p.user.clone(),
+                    command: p.command.clone(),
+                    cpu_percent: p.cpu_usage,
+                    memory_bytes: p.mem_bytes,
+                })
+                .collect(),
+            data_captured_at: staleness.map(|(at, _)| at.format("%Y-%m-%d %H:%M:%S").to_string()),
+            stale_seconds: staleness.map(|(_, age)| age),
+        }
+        .to_json();
 
-        match fs::write(&filename, snapshot_data) {
-            Ok(_) => {
-                self.status_message = Some(format!("📄 Snapshot exported to: {}", filename));
+        let compress = self.config.export_compress;
+        Box::new(move || {
+            write_export(Path::new(&filename), snapshot_data.as_bytes(), compress)
+                .map(|_| filename)
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    // Show the Export overlay with a spinner and queue the write for a blocking task
+    fn start_export(&mut self, target: ExportTarget) {
+        self.overlay = Overlay::Export;
+        if matches!(self.export_state, ExportState::Running { .. }) {
+            return;
+        }
+        match self.export_to(target) {
+            Ok(job) => {
+                self.export_state = ExportState::Running {
+                    started: Instant::now(),
+                };
+                self.export_job = Some(job);
+                self.status_message = Some(format!("{} Exporting...", self.icon("📤", "[export]")));
+            }
+            Err(e) => self.finish_export(Err(e)),
+        }
+    }
+
+    // Report an export outcome in the status line and the Export overlay
+    fn finish_export(&mut self, result: Result<String, String>) {
+        let started = match self.export_state {
+            ExportState::Running { started } => started,
+            _ => Instant::now(),
+        };
+        self.export_state = match result {
+            Ok(path) => {
+                let bytes = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                self.status_message = Some(format!(
+                    "{} Snapshot exported to: {}",
+                    self.icon("📄", "[export]"),
+                    path
+                ));
+                ExportState::Done {
+                    path,
+                    bytes,
+                    elapsed: started.elapsed(),
+                }
             }
             Err(e) => {
-                self.status_message = Some(format!("❌ Export failed: {}", e));
+                self.status_message = Some(format!(
+                    "{} Export failed: {}",
+                    self.icon("❌", "[error]"),
+                    e
+                ));
+                ExportState::Failed(e)
             }
+        };
+    }
+
+    // Offer built-in JSON plus plugin formats; with no plugins there is nothing to choose
+    fn open_export_menu(&mut self) {
+        let mut targets = vec![ExportTarget::Json];
+        targets.extend(
+            self.plugins
+                .exporter_formats()
+                .into_iter()
+                .map(|(name, format)| ExportTarget::Plugin { name, format }),
+        );
+        if targets.len() == 1 {
+            self.start_export(ExportTarget::Json);
+            return;
         }
+        // Keep the previous choice selected when it is still offered
+        let previous = self.export_targets.get(self.export_selected).cloned();
+        self.export_selected = previous
+            .and_then(|t| targets.iter().position(|x| *x == t))
+            .unwrap_or(0);
+        self.export_targets = targets;
+        self.overlay = Overlay::ExportMenu;
     }
 
-    fn show_ai_insights(&mut self) {
+    // Processes for an export, scrubbed when --anonymize is in effect
+    fn export_processes(&mut self, limit: usize) -> Vec<monitor::ProcessInfo> {
+        let mut procs = self.collect_processes(limit);
+        self.sanitize_for_export(&mut procs);
+        procs
+    }
+
+    // Everything that leaves the machine goes through here: --anonymize, then the length cap
+    fn sanitize_for_export(&self, procs: &mut [monitor::ProcessInfo]) {
+        if self.anonymize_exports {
+            anonymize_processes(procs);
+        }
+        let max = self.config.export_max_command_chars;
+        if max > 0 {
+            for p in procs.iter_mut() {
+                p.command = truncate_chars(&p.command, max).into_owned();
+            }
+        }
+    }
+
+    fn export_to(&mut self, target: ExportTarget) -> Result<ExportJob, String> {
+        match target {
+            ExportTarget::Json => Ok(self.export_snapshot()),
+            ExportTarget::Plugin { name, format } => {
+                let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+                let filename = format!("lyvoxa_snapshot_{}.{}", timestamp, format);
+                // Reuse the last displayed sample; re-reading would reset the network rate window
+                let mut snapshot = self.last_snapshot.clone();
+                snapshot.top_processes = self
+                    .export_processes(10)
+                    .into_iter()
+                    .map(|p| plugin::ProcessSummary {
+                        pid: p.pid,
+                        user: p.user,
+                        command: p.command,
+                        cpu_percent: p.cpu_usage,
+                        memory_bytes: p.mem_bytes,
+                    })
+                    .collect();
+                let exporter = self.plugins.exporter(&name).map_err(|e| e.to_string())?;
+                Ok(Box::new(move || {
+                    exporter
+                        .export(&snapshot, &filename)
+                        .map(|_| filename)
+                        .map_err(|e| e.to_string())
+                }))
+            }
+        }
+    }
+
+    // Top CPU consumers (whole process table, not the filtered view) plus the top memory user
+    fn insight_processes(&self) -> (Vec<monitor::ProcessInfo>, Option<monitor::ProcessInfo>) {
+        let top_n = self.config.insights_top_n.max(1);
+        let mut ranked: Vec<&monitor::ProcessInfo> = self.processes.iter().collect();
+        ranked.sort_by(|a, b| {
+            b.cpu_usage
+                .partial_cmp(&a.cpu_usage)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let top_processes = ranked.into_iter().take(top_n).cloned().collect();
+        let top_mem = self.processes.iter().max_by_key(|p| p.mem_bytes).cloned();
+        (top_processes, top_mem)
+    }
+
+    fn build_insights(
+        &self,
+        top_processes: &[monitor::ProcessInfo],
+        top_mem: Option<&monitor::ProcessInfo>,
+    ) -> Vec<String> {
         // AI-assisted insights based on current system state
         let mut insights = Vec::new();
 
@@ -722,32 +2503,85 @@ impl App {
         } else {
             0.0
         };
-        let top_processes = self.collect_processes(3); // Only need top 3 for insights
 
         // CPU Analysis
         if cpu_usage > 80.0 {
-            insights.push("⚠️  HIGH CPU: System under heavy load".to_string());
+            insights.push(format!(
+                "{} HIGH CPU: System under heavy load",
+                self.icon("⚠️ ", "[!]")
+            ));
             if let Some(proc) = top_processes.first()
                 && proc.cpu_usage > 50.0
             {
                 insights.push(format!(
-                    "🔥 Top CPU hog: {} ({:.1}%)",
-                    proc.command, proc.cpu_usage
+                    "{} Top CPU hog: {} ({:.1}%)",
+                    self.icon("🔥", "[cpu]"),
+                    proc.command,
+                    proc.cpu_usage
                 ));
             }
         } else if cpu_usage < 10.0 {
-            insights.push("✅ CPU: System running efficiently".to_string());
+            insights.push(format!(
+                "{} CPU: System running efficiently",
+                self.icon("✅", "[ok]")
+            ));
         }
 
-        // Memory Analysis
-        if memory_usage > 85.0 {
-            insights.push("⚠️  HIGH MEMORY: Consider closing applications".to_string());
-            if let Some(proc) = top_processes.iter().max_by_key(|p| p.mem_bytes) {
+        // Memory Analysis: judged by swapping and stalls, not just how full RAM is
+        let (swap_in, swap_out) = self.swap_rates;
+        let psi = self.monitor.get_memory_pressure();
+        let pressure = memory_pressure_level(swap_in, swap_out, psi);
+        let evidence = match psi {
+            Some(p) => format!(
+                "swap {:.0} in/{:.0} out pages/s, stalls {:.1}% some/{:.1}% full",
+                swap_in, swap_out, p.some_avg10, p.full_avg10
+            ),
+            None => format!("swap {:.0} in/{:.0} out pages/s", swap_in, swap_out),
+        };
+        match pressure {
+            MemoryPressureLevel::Severe => insights.push(format!(
+                "{} MEMORY UNDER PRESSURE at {:.0}% used ({})",
+                self.icon("⚠️ ", "[!]"),
+                memory_usage,
+                evidence
+            )),
+            MemoryPressureLevel::Moderate => insights.push(format!(
+                "{} Memory pressure building at {:.0}% used ({})",
+                self.icon("⚠️ ", "[!]"),
+                memory_usage,
+                evidence
+            )),
+            MemoryPressureLevel::None if memory_usage > 85.0 => insights.push(format!(
+                "{} HIGH MEMORY use but no pressure: no swapping or stalls",
+                self.icon("ℹ️ ", "[i]")
+            )),
+            MemoryPressureLevel::None => {}
+        }
+        // Commit charge predicts OOM risk: it counts reservations that haven't been touched yet
+        if let Some(commit) = self.monitor.get_commit_info()
+            && commit.ratio_percent() >= 100.0
+        {
+            insights.push(format!(
+                "{} OVERCOMMIT: {:.0}% of the commit limit promised to processes (OOM risk if used)",
+                self.icon("⚠️ ", "[!]"),
+                commit.ratio_percent()
+            ));
+        }
+        if pressure != MemoryPressureLevel::None || memory_usage > 85.0 {
+            if let Some(proc) = top_mem {
                 let mem_mb = proc.mem_bytes / (1024 * 1024);
-                insights.push(format!("💾 Memory hog: {} ({} MB)", proc.command, mem_mb));
+                insights.push(format!(
+                    "{} Memory hog: {} ({} MB)",
+                    self.icon("💾", "[mem]"),
+                    proc.command,
+                    mem_mb
+                ));
             }
         } else if memory_usage < 50.0 {
-            insights.push("✅ MEMORY: Plenty of free memory available".to_string());
+            insights.push(format!(
+                "{} MEMORY: Plenty of free memory available",
+                self.icon("✅", "[ok]")
+            ));
         }
 
         // Process Analysis
@@ -756,100 +2590,581 @@ impl App {
             .filter(|p| p.cpu_usage > 20.0)
             .collect();
         if high_cpu_procs.len() > 3 {
-            insights.push("⚡ Multiple high-CPU processes detected".to_string());
+            insights.push(format!(
+                "{} Multiple high-CPU processes detected",
+                self.icon("⚡", "[!]")
+            ));
         }
 
         // Network Analysis
         if let (Some(&rx), Some(&tx)) = (self.net_rx_history.back(), self.net_tx_history.back()) {
             let total_mb_s = (rx + tx) / (1024.0 * 1024.0);
             if total_mb_s > 10.0 {
-                insights.push(format!("🌐 HIGH NETWORK: {:.1} MB/s total", total_mb_s));
+                insights.push(format!(
+                    "{} HIGH NETWORK: {:.1} MB/s total",
+                    self.icon("🌐", "[net]"),
+                    total_mb_s
+                ));
             }
         }
 
         // Performance recommendations
+        let tip = self.icon("💡", "[tip]");
+        let arrow = self.icon("→", "->");
         if cpu_usage > 70.0 && memory_usage > 70.0 {
-            insights.push("💡 RECOMMENDATION: System bottleneck detected".to_string());
-            insights.push("   → Consider upgrading hardware or closing applications".to_string());
+            insights.push(format!(
+                "{} RECOMMENDATION: System bottleneck detected",
+                tip
+            ));
+            insights.push(format!(
+                "   {} Consider upgrading hardware or closing applications",
+                arrow
+            ));
         } else if cpu_usage > 70.0 {
-            insights.push("💡 RECOMMENDATION: CPU-bound workload".to_string());
-            insights.push("   → Check for background processes or heavy computations".to_string());
-        } else if memory_usage > 70.0 {
-            insights.push("💡 RECOMMENDATION: Memory pressure".to_string());
-            insights.push("   → Close unused applications or browser tabs".to_string());
+            insights.push(format!("{} RECOMMENDATION: CPU-bound workload", tip));
+            insights.push(format!(
+                "   {} Check for background processes or heavy computations",
+                arrow
+            ));
+        } else if pressure != MemoryPressureLevel::None {
+            insights.push(format!("{} RECOMMENDATION: Memory pressure", tip));
+            insights.push(format!(
+                "   {} Close unused applications or browser tabs",
+                arrow
+            ));
         }
 
         if insights.is_empty() {
-            insights.push("✨ SYSTEM OPTIMAL: Everything looks good!".to_string());
-            insights.push("🚀 Performance is within normal ranges".to_string());
+            insights.push(format!(
+                "{} SYSTEM OPTIMAL: Everything looks good!",
+                self.icon("✨", "[ok]")
+            ));
+            insights.push(format!(
+                "{} Performance is within normal ranges",
+                self.icon("🚀", "[ok]")
+            ));
+        }
+
+        insights
+    }
+
+    fn show_ai_insights(&mut self) {
+        let (top_processes, top_mem) = self.insight_processes();
+        let mut insights = self.build_insights(&top_processes, top_mem.as_ref());
+
+        // Top consumers are always reported so there is something concrete to act on
+        insights.push(String::new());
+        insights.push(format!("Top {} by CPU:", top_processes.len()));
+        for p in &top_processes {
+            insights.push(format!(
+                "  {:>7} {:>5.1}%  {}",
+                p.pid,
+                p.cpu_usage,
+                truncate_chars(&p.command, 60)
+            ));
+        }
+        if let Some(p) = &top_mem {
+            insights.push(format!(
+                "Top memory: {} ({} MB, PID {})",
+                truncate_chars(&p.command, 60),
+                p.mem_bytes / (1024 * 1024),
+                p.pid
+            ));
         }
 
         self.overlay = Overlay::Insights;
         self.status_message = Some(insights.join("\n"));
     }
 
-    fn update_fast(&mut self) {
-        self.monitor.refresh_fast();
+    // Shareable Markdown report: metrics table, insight bullets and top processes
+    fn export_insights_markdown(&mut self) {
+        use chrono::{DateTime, Local};
 
-        let cpu_usage = self.monitor.get_global_cpu_usage();
-        self.cpu_history.push_back(cpu_usage);
-        if self.cpu_history.len() > 30 {
-            self.cpu_history.pop_front();
-        }
+        let now: DateTime<Local> = Local::now();
+        let filename = format!("lyvoxa_insights_{}.md", now.format("%Y%m%d_%H%M%S"));
 
-        let memory_usage = self.monitor.get_memory_usage_percent();
-        self.memory_history.push_back(memory_usage);
-        if self.memory_history.len() > 30 {
-            self.memory_history.pop_front();
-        }
+        // One pass over both lists so a pseudonym means the same user in every table
+        let (mut top_processes, top_mem) = self.insight_processes();
+        let has_top_mem = top_mem.is_some();
+        top_processes.extend(top_mem);
+        self.sanitize_for_export(&mut top_processes);
+        let top_mem = if has_top_mem {
+            top_processes.pop()
+        } else {
+            None
+        };
+        let insights = self.build_insights(&top_processes, top_mem.as_ref());
+        let cpu_usage = self.cpu_history.back().copied().unwrap_or(0.0);
+        let memory_usage = self.memory_history.back().copied().unwrap_or(0.0);
+        let rx = self.net_rx_history.back().copied().unwrap_or(0.0);
+        let tx = self.net_tx_history.back().copied().unwrap_or(0.0);
+        let (used_swap, total_swap) = self.monitor.get_swap_info();
+        let (load1, load5, load15) = self.monitor.get_load_average();
+        let info = self.monitor.get_system_info();
 
-        let (rx, tx) = self.monitor.get_network_rates();
-        self.net_rx_history.push_back(rx);
-        self.net_tx_history.push_back(tx);
-        if self.net_rx_history.len() > 30 {
-            self.net_rx_history.pop_front();
+        let mut md = String::new();
+        md.push_str("# Lyvoxa Insights Report\n\n");
+        md.push_str(&format!(
+            "- **Generated:** {}\n",
+            now.format("%Y-%m-%d %H:%M:%S %Z")
+        ));
+        if !self.anonymize_exports {
+            md.push_str(&format!("- **Host:** {}\n", info.hostname));
         }
-        if self.net_tx_history.len() > 30 {
-            self.net_tx_history.pop_front();
+        md.push_str(&format!("- **Kernel:** {}\n", info.kernel_version));
+        md.push_str(&format!("- **Lyvoxa:** v{}\n", VERSION));
+        if let Some((at, age)) = self.data_staleness() {
+            md.push_str(&format!(
+                "- **Data:** FROZEN at {} ({:.0}s old, manual refresh mode)\n",
+                at.format("%Y-%m-%d %H:%M:%S %Z"),
+                age
+            ));
         }
+        md.push('\n');
 
-        self.last_update = Instant::now();
-    }
+        md.push_str("## Metrics\n\n| Metric | Value |\n| --- | --- |\n");
+        md.push_str(&format!("| CPU usage | {:.1}% |\n", cpu_usage));
+        md.push_str(&format!("| Memory usage | {:.1}% |\n", memory_usage));
+        md.push_str(&format!(
+            "| Swap | {} / {} |\n",
+            humansize::format_size(used_swap, humansize::DECIMAL),
+            humansize::format_size(total_swap, humansize::DECIMAL)
+        ));
+        md.push_str(&format!(
+            "| Load average | {:.2} {:.2} {:.2} |\n",
+            load1, load5, load15
+        ));
+        md.push_str(&format!(
+            "| Network RX | {}/s |\n",
+            humansize::format_size(rx as u64, humansize::DECIMAL)
+        ));
+        md.push_str(&format!(
+            "| Network TX | {}/s |\n",
+            humansize::format_size(tx as u64, humansize::DECIMAL)
+        ));
+        md.push_str(&format!("| Processes | {} |\n\n", info.process_count));
 
-    fn update_slow(&mut self) {
-        self.monitor.refresh_slow();
-        self.processes = self.monitor.get_processes();
-        self.process_view_dirty = true;
-    }
+        md.push_str("## Insights\n\n");
+        for line in &insights {
+            let line = line.trim();
+            if !line.is_empty() {
+                md.push_str(&format!("- {}\n", line));
+            }
+        }
+
+        md.push_str("\n## Top Processes (by CPU)\n\n");
+        md.push_str(
+            "| PID | User | CPU% | Memory | Command |\n| ---: | --- | ---: | ---: | --- |\n",
+        );
+        for p in top_processes.iter().chain(top_mem.iter()) {
+            md.push_str(&format!(
+                "| {} | {} | {:.1} | {} | `{}` |\n",
+                p.pid,
+                p.user,
+                p.cpu_usage,
+                humansize::format_size(p.mem_bytes, humansize::DECIMAL),
+                truncate_chars(&p.command, 120)
+                    .replace('|', "\\|")
+                    .replace('`', "'")
+            ));
+        }
+
+        self.status_message = Some(match fs::write(&filename, md) {
+            Ok(_) => format!(
+                "{} Insights report written to: {}",
+                self.icon("📝", "[export]"),
+                filename
+            ),
+            Err(e) => format!(
+                "{} Insights export failed: {}",
+                self.icon("❌", "[error]"),
+                e
+            ),
+        });
+        self.overlay = Overlay::None;
+    }
+
+    fn update_fast(&mut self) {
+        self.monitor.refresh_fast();
+        if self.overlay == Overlay::Network {
+            self.iface_rates = self.monitor.get_network_rates_per_iface();
+        }
+
+        // Route the sample through processor plugins; their output is what gets displayed
+        let raw = self.system_snapshot();
+        let snapshot = match self.plugins.process_data(raw.clone()) {
+            Ok(processed) => processed,
+            Err(e) => {
+                self.status_message = Some(format!("Processor plugin failed: {}", e));
+                raw
+            }
+        };
+
+        self.last_snapshot = snapshot.clone();
+        let history_len = self.config.history_len.max(2);
+        self.cpu_history.push_back(snapshot.cpu_usage);
+        while self.cpu_history.len() > history_len {
+            self.cpu_history.pop_front();
+        }
+
+        self.memory_history.push_back(snapshot.memory_usage);
+        while self.memory_history.len() > history_len {
+            self.memory_history.pop_front();
+        }
+        let (used_swap, total_swap) = self.monitor.get_swap_info();
+        self.swap_history.push_back(if total_swap > 0 {
+            used_swap as f64 / total_swap as f64 * 100.0
+        } else {
+            0.0
+        });
+        while self.swap_history.len() > history_len {
+            self.swap_history.pop_front();
+        }
+
+        self.net_rx_history.push_back(snapshot.network_rx);
+        self.net_tx_history.push_back(snapshot.network_tx);
+        while self.net_rx_history.len() > history_len {
+            self.net_rx_history.pop_front();
+        }
+        while self.net_tx_history.len() > history_len {
+            self.net_tx_history.pop_front();
+        }
+
+        self.process_count_history
+            .push_back(snapshot.process_count as f64);
+        while self.process_count_history.len() > history_len {
+            self.process_count_history.pop_front();
+        }
+
+        self.last_update = Instant::now();
+        self.history_times.push_back(self.last_update);
+        while self.history_times.len() > history_len {
+            self.history_times.pop_front();
+        }
+    }
+
+    fn set_plugin_metrics(&mut self, metrics: HashMap<String, f64>, errors: Vec<String>) {
+        self.plugin_metrics_pending = false;
+        // Throttled plugins are absent from this batch; keep their previous values
+        self.plugin_metrics.extend(metrics);
+        if let Some(first) = errors.first() {
+            self.status_message = Some(format!("Plugin metrics failed: {}", first));
+        }
+    }
+
+    // Current metrics in the shape plugins consume
+    fn system_snapshot(&mut self) -> plugin::SystemSnapshot {
+        let (network_rx, network_tx) = self.monitor.get_network_rates();
+        plugin::SystemSnapshot {
+            cpu_usage: self.monitor.get_global_cpu_usage(),
+            memory_usage: self.monitor.get_memory_usage_percent(),
+            network_rx,
+            network_tx,
+            process_count: self.monitor.get_process_count(),
+            uptime_seconds: self.monitor.get_uptime(),
+            load_average: self.monitor.get_load_average(),
+            cpu_temperature: self.monitor.get_cpu_temperature(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            top_processes: Vec::new(),
+        }
+    }
+
+    // Seconds covered by the history window, used as the chart x-axis extent
+    fn history_span_secs(&self) -> f64 {
+        match (self.history_times.front(), self.history_times.back()) {
+            (Some(first), Some(last)) => last
+                .saturating_duration_since(*first)
+                .as_secs_f64()
+                .max(1.0),
+            _ => 1.0,
+        }
+    }
+
+    // Pair samples with their real elapsed time so skipped ticks show as gaps, not even spacing
+    fn timed_series(&self, values: &VecDeque<f64>) -> Vec<(f64, f64)> {
+        let Some(&origin) = self.history_times.front() else {
+            return Vec::new();
+        };
+        self.history_times
+            .iter()
+            .zip(values.iter())
+            .map(|(t, &v)| (t.saturating_duration_since(origin).as_secs_f64(), v))
+            .collect()
+    }
+
+    // History index and chart x (seconds) under the inspection cursor
+    fn chart_cursor_point(&self) -> Option<(usize, f64)> {
+        let back = self.chart_cursor?;
+        let len = self.history_times.len();
+        if len == 0 {
+            return None;
+        }
+        let idx = len - 1 - back.min(len - 1);
+        let origin = *self.history_times.front()?;
+        let x = self.history_times[idx]
+            .saturating_duration_since(origin)
+            .as_secs_f64();
+        Some((idx, x))
+    }
+
+    // Seconds between the cursor sample and the newest sample
+    fn chart_cursor_age_secs(&self, idx: usize) -> f64 {
+        match (self.history_times.get(idx), self.history_times.back()) {
+            (Some(at), Some(last)) => last.saturating_duration_since(*at).as_secs_f64(),
+            _ => 0.0,
+        }
+    }
+
+    // In manual refresh mode the data is frozen between 'r' presses: when it was captured and
+    // how old it is, so an export can't pass for live data. None while refreshing normally
+    fn data_staleness(&self) -> Option<(chrono::DateTime<chrono::Local>, f64)> {
+        if !self.config.manual_refresh {
+            return None;
+        }
+        let at = self.data_refreshed_at?;
+        let age = (chrono::Local::now() - at).num_milliseconds().max(0) as f64 / 1000.0;
+        Some((at, age))
+    }
+
+    // Explicit refresh ('r'): the only way data changes in manual refresh mode
+    fn refresh_now(&mut self) {
+        self.update_slow();
+        self.update_processes();
+        self.update_fast();
+        self.process_view_dirty = true;
+    }
+
+    fn update_slow(&mut self) {
+        self.monitor.refresh_slow();
+        self.swap_rates = self.monitor.get_swap_rates();
+        self.cpu_temp = self.monitor.get_cpu_temperature();
+        self.open_files = self.monitor.get_open_files();
+    }
+
+    fn update_processes(&mut self) {
+        self.monitor.refresh_processes();
+        self.data_refreshed_at = Some(chrono::Local::now());
+        let processes = self.monitor.get_processes();
+        self.diff_processes(&processes);
+        self.update_deviation(&processes);
+        // A pin still set means the view wasn't rebuilt since the last refresh, so the
+        // view indices no longer match `processes`; keep the earlier pin instead
+        if self.pinned_pid.is_none() {
+            self.pinned_pid = self.pid_at(self.selected);
+        }
+        self.processes = processes;
+        if self.overlay == Overlay::Detail
+            && let Some(pid) = self.detail_pid
+        {
+            self.detail_wchan = self.monitor.get_wchan(pid);
+        }
+        self.process_view_dirty = true;
+    }
+
+    // Flag PIDs that appeared since the previous refresh and log the ones that went away
+    fn diff_processes(&mut self, current: &[monitor::ProcessInfo]) {
+        // First refresh: everything is "new", nothing worth flagging
+        if self.processes.is_empty() {
+            self.new_pids.clear();
+            return;
+        }
+        let prev_pids: HashSet<u32> = self.processes.iter().map(|p| p.pid).collect();
+        let cur_pids: HashSet<u32> = current.iter().map(|p| p.pid).collect();
+
+        let started: Vec<&monitor::ProcessInfo> = current
+            .iter()
+            .filter(|p| !prev_pids.contains(&p.pid))
+            .collect();
+        let exited: Vec<&monitor::ProcessInfo> = self
+            .processes
+            .iter()
+            .filter(|p| !cur_pids.contains(&p.pid))
+            .collect();
+
+        let mut events = Vec::new();
+        if started.len() > 5 {
+            events.push(format!("{} processes started", started.len()));
+        } else {
+            for p in &started {
+                events.push(format!("Started: PID {} ({})", p.pid, p.command));
+            }
+        }
+        if exited.len() > 5 {
+            events.push(format!("{} processes exited", exited.len()));
+        } else {
+            for p in &exited {
+                events.push(format!("Exited: PID {} ({})", p.pid, p.command));
+            }
+        }
+
+        self.new_pids = started.iter().map(|p| p.pid).collect();
+        for e in events {
+            self.log_event(e);
+        }
+    }
+
+    // Score each PID by |current - own recent mean| over CPU% and MEM%, then record the sample
+    fn update_deviation(&mut self, current: &[monitor::ProcessInfo]) {
+        let live: HashSet<u32> = current.iter().map(|p| p.pid).collect();
+        self.proc_history.retain(|pid, _| live.contains(pid));
+        self.deviation.clear();
+
+        for p in current {
+            let hist = self
+                .proc_history
+                .entry(p.pid)
+                .or_insert_with(|| VecDeque::with_capacity(PROC_HISTORY_LEN));
+            if !hist.is_empty() {
+                let n = hist.len() as f32;
+                let mean_cpu = hist.iter().map(|(c, _)| c).sum::<f32>() / n;
+                let mean_mem = hist.iter().map(|(_, m)| m).sum::<f32>() / n;
+                let score = (p.cpu_usage - mean_cpu).abs() + (p.mem_percent - mean_mem).abs();
+                self.deviation.insert(p.pid, score);
+            }
+            hist.push_back((p.cpu_usage, p.mem_percent));
+            if hist.len() > PROC_HISTORY_LEN {
+                hist.pop_front();
+            }
+        }
+    }
+
+    fn log_event(&mut self, msg: String) {
+        let ts = chrono::Local::now().format("%H:%M:%S");
+        self.event_log.push_back(format!("{} {}", ts, msg));
+        if self.event_log.len() > 200 {
+            self.event_log.pop_front();
+        }
+    }
+
+    // Left click selects a process row, the wheel moves the selection like Up/Down
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if !matches!(
+            mouse.kind,
+            MouseEventKind::Down(MouseButton::Left)
+                | MouseEventKind::ScrollUp
+                | MouseEventKind::ScrollDown
+        ) {
+            return;
+        }
+        self.last_input = Instant::now();
+        if self.screensaver.take().is_some() {
+            self.rates_changed = true;
+            return;
+        }
+        if self.overlay != Overlay::None {
+            return;
+        }
+        match mouse.kind {
+            MouseEventKind::ScrollUp => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            MouseEventKind::ScrollDown => {
+                self.selected = self.selected.saturating_add(1);
+            }
+            _ => {
+                let (area, offset) = self.process_rows_area.get();
+                if area.contains((mouse.column, mouse.row).into()) {
+                    let row = offset + (mouse.row - area.y) as usize;
+                    if row < self.process_view.len() {
+                        self.selected = row;
+                    }
+                }
+            }
+        }
+    }
 
     fn handle_key(&mut self, key: KeyEvent) {
+        self.last_input = Instant::now();
+        // The key that wakes the screensaver only dismisses it
+        if self.screensaver.take().is_some() {
+            self.rates_changed = true;
+            return;
+        }
+        let overlay_at_entry = self.overlay;
         match self.overlay {
             Overlay::Search | Overlay::Filter => match key.code {
                 KeyCode::Esc => {
                     self.overlay = Overlay::None;
                     self.input_buffer.clear();
                 }
+                KeyCode::Tab => {
+                    let mode = self.match_mode().next();
+                    self.config.match_mode = Some(mode.as_str().to_string());
+                    let _ = save_config_file_at(&self.config_path, &self.config);
+                    self.process_view_dirty = true;
+                }
                 KeyCode::Enter => {
+                    // Keep the prompt open so the pattern can be fixed
+                    if let Err(e) = TermMatcher::new(self.input_buffer.trim(), self.match_mode()) {
+                        self.status_message = Some(format!("Invalid regex: {}", e));
+                        return;
+                    }
                     match self.overlay {
                         Overlay::Search => {
                             self.search = self.input_buffer.clone();
                         }
                         Overlay::Filter => {
-                            self.filter = self.input_buffer.clone();
+                            self.add_filter(self.input_buffer.clone());
                         }
                         _ => {}
                     }
+                    self.process_view_dirty = true;
+                    self.clamp_selection();
                     self.overlay = Overlay::None;
                     self.input_buffer.clear();
                 }
+                KeyCode::Backspace
+                    if self.overlay == Overlay::Filter && self.input_buffer.is_empty() =>
+                {
+                    // Backspace on an empty prompt peels off the last chip
+                    if !self.filters.is_empty() {
+                        self.remove_filter(self.filters.len() - 1);
+                    }
+                }
                 KeyCode::Backspace => {
                     self.input_buffer.pop();
                 }
+                KeyCode::Up if self.overlay == Overlay::Filter => {
+                    self.filter_selected = self.filter_selected.saturating_sub(1);
+                }
+                KeyCode::Down if self.overlay == Overlay::Filter => {
+                    if self.filter_selected + 1 < self.filters.len() {
+                        self.filter_selected += 1;
+                    }
+                }
+                KeyCode::Delete if self.overlay == Overlay::Filter => {
+                    if self.filter_selected < self.filters.len() {
+                        self.remove_filter(self.filter_selected);
+                    }
+                }
                 KeyCode::Char(c) => {
                     self.input_buffer.push(c);
                 }
                 _ => {}
             },
+            Overlay::ExportMenu => match key.code {
+                KeyCode::Esc => {
+                    self.overlay = Overlay::None;
+                }
+                KeyCode::Up => {
+                    self.export_selected = self.export_selected.saturating_sub(1);
+                }
+                KeyCode::Down => {
+                    if self.export_selected + 1 < self.export_targets.len() {
+                        self.export_selected += 1;
+                    }
+                }
+                KeyCode::Enter => {
+                    self.overlay = Overlay::None;
+                    if let Some(target) = self.export_targets.get(self.export_selected).cloned() {
+                        self.start_export(target);
+                    }
+                }
+                _ => {}
+            },
             Overlay::Setup => match key.code {
                 KeyCode::Esc => {
                     self.overlay = Overlay::None;
@@ -865,23 +3180,177 @@ impl App {
                     }
                 }
                 KeyCode::Enter => {
-                    self.apply_selected_config();
+                    // May reopen as Confirm when the candidate has to be created
                     self.overlay = Overlay::None;
+                    self.apply_selected_config();
                 }
                 KeyCode::Char('r') | KeyCode::Char('R') => {
                     self.refresh_config_candidates();
                 }
+                KeyCode::Char('e') | KeyCode::Char('E') => {
+                    self.open_config_editor();
+                }
+                KeyCode::Char('D') => {
+                    // The active file as resolved at startup (or last switched to), not the highlighted one
+                    self.pending_action = Some(PendingAction::ResetConfig {
+                        path: self.config_path.clone(),
+                    });
+                    self.overlay = Overlay::Confirm;
+                }
                 _ => {}
             },
-            Overlay::Help | Overlay::Insights | Overlay::Export => match key.code {
+            Overlay::BatchNice => match key.code {
+                KeyCode::Esc => {
+                    self.overlay = Overlay::None;
+                    self.input_buffer.clear();
+                }
+                KeyCode::Enter => {
+                    // May reopen as Confirm with the matched processes
+                    self.overlay = Overlay::None;
+                    let input = std::mem::take(&mut self.input_buffer);
+                    self.prepare_batch_renice(&input);
+                }
+                KeyCode::Backspace => {
+                    self.input_buffer.pop();
+                }
+                KeyCode::Char(c) if c.is_ascii_digit() || c == '-' => {
+                    self.input_buffer.push(c);
+                }
+                _ => {}
+            },
+            Overlay::Signal => match key.code {
+                KeyCode::Esc => {
+                    self.signal_pid = None;
+                    self.overlay = Overlay::None;
+                }
+                KeyCode::Up => {
+                    self.signal_selected = self.signal_selected.saturating_sub(1);
+                }
+                KeyCode::Down => {
+                    if self.signal_selected + 1 < SIGNAL_CHOICES.len() {
+                        self.signal_selected += 1;
+                    }
+                }
+                KeyCode::Enter => {
+                    self.send_selected_signal();
+                    self.overlay = Overlay::None;
+                }
+                KeyCode::Backspace => {
+                    self.input_buffer.pop();
+                }
+                KeyCode::Char(c) if c.is_ascii_alphanumeric() => {
+                    self.input_buffer.push(c);
+                }
+                _ => {}
+            },
+            Overlay::ConfigEditor if self.editor_editing => match key.code {
+                KeyCode::Esc => {
+                    self.editor_editing = false;
+                    self.input_buffer.clear();
+                }
+                KeyCode::Enter => {
+                    let input = std::mem::take(&mut self.input_buffer);
+                    self.edit_config_field(&input);
+                    self.editor_editing = false;
+                }
+                KeyCode::Backspace => {
+                    self.input_buffer.pop();
+                }
+                KeyCode::Char(c) => {
+                    self.input_buffer.push(c);
+                }
+                _ => {}
+            },
+            Overlay::ConfigEditor => match key.code {
+                KeyCode::Esc => self.close_config_editor(false),
+                KeyCode::Char('s') | KeyCode::Char('S') => self.close_config_editor(true),
+                KeyCode::Up => {
+                    self.editor_selected = self.editor_selected.saturating_sub(1);
+                }
+                KeyCode::Down => {
+                    if self.editor_selected + 1 < CONFIG_EDITOR_FIELDS.len() {
+                        self.editor_selected += 1;
+                    }
+                }
+                KeyCode::Enter => {
+                    let key = CONFIG_EDITOR_FIELDS[self.editor_selected];
+                    self.input_buffer = config_field_value(&self.config, key).unwrap_or_default();
+                    self.editor_editing = true;
+                }
+                KeyCode::Char(' ') => {
+                    // Booleans toggle in place
+                    let key = CONFIG_EDITOR_FIELDS[self.editor_selected];
+                    match config_field_value(&self.config, key).as_deref() {
+                        Some("true") => self.edit_config_field("false"),
+                        Some("false") => self.edit_config_field("true"),
+                        _ => {}
+                    }
+                }
+                KeyCode::Char('d') => {
+                    // Empty input drops the key, so serde fills in the default
+                    self.edit_config_field("");
+                }
+                _ => {}
+            },
+            Overlay::Confirm => {
+                // Kills, restarts (SIGTERM too) and batch actions take an explicit 'y'; a stray
+                // Enter is exactly the accident this guards
+                let is_kill = matches!(
+                    self.pending_action,
+                    Some(
+                        PendingAction::Kill { .. }
+                            | PendingAction::Restart { .. }
+                            | PendingAction::BatchRenice { .. }
+                    )
+                );
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        self.confirm_pending_action();
+                    }
+                    KeyCode::Enter if !is_kill => {
+                        self.confirm_pending_action();
+                    }
+                    _ => {
+                        self.pending_action = None;
+                        self.status_message = Some("Cancelled".to_string());
+                    }
+                }
+                self.overlay = Overlay::None;
+            }
+            Overlay::Help
+            | Overlay::Insights
+            | Overlay::Export
+            | Overlay::Detail
+            | Overlay::Events
+            | Overlay::SystemInfo
+            | Overlay::Network
+            | Overlay::Disks => match key.code {
                 KeyCode::Esc | KeyCode::Enter => {
                     self.overlay = Overlay::None;
                 }
+                KeyCode::Char('m') if self.overlay == Overlay::Insights => {
+                    self.export_insights_markdown();
+                }
                 _ => {}
             },
             _ => {}
         }
 
+        // Typed characters belong to the prompt/dialog, not to global shortcuts
+        if matches!(
+            overlay_at_entry,
+            Overlay::Search
+                | Overlay::Filter
+                | Overlay::Confirm
+                | Overlay::ExportMenu
+                | Overlay::Setup
+                | Overlay::ConfigEditor
+                | Overlay::Signal
+                | Overlay::BatchNice
+        ) {
+            return;
+        }
+
         match key.code {
             KeyCode::Char('q') | KeyCode::F(10) => {
                 let _ = save_config_file_at(&self.config_path, &self.config);
@@ -908,10 +3377,14 @@ impl App {
             }
             KeyCode::F(4) => {
                 self.overlay = Overlay::Filter;
-                self.input_buffer = self.filter.clone();
+                self.input_buffer = String::new();
+                self.filter_selected = self.filters.len().saturating_sub(1);
             }
             KeyCode::F(5) => {
                 self.config.show_charts = !self.config.show_charts;
+                if !self.config.show_charts {
+                    self.chart_cursor = None;
+                }
                 self.status_message = Some(if self.config.show_charts {
                     "Charts: ON".to_string()
                 } else {
@@ -919,119 +3392,906 @@ impl App {
                 });
                 let _ = save_config_file_at(&self.config_path, &self.config);
             }
+            // Shift+F6 arrives as F18 on terminals that encode shifted function keys
+            KeyCode::F(6) if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.toggle_sort_direction();
+            }
+            KeyCode::F(18) | KeyCode::Char('o') => {
+                self.toggle_sort_direction();
+            }
             KeyCode::F(6) => {
+                let selected_pid = self.selected_pid();
                 self.sort_key = match self.sort_key {
                     SortKey::Cpu => SortKey::Mem,
-                    SortKey::Mem => SortKey::Pid,
-                    SortKey::Pid => SortKey::User,
+                    SortKey::Mem => SortKey::Virt,
+                    SortKey::Virt => SortKey::Shr,
+                    SortKey::Shr => SortKey::DiskRead,
+                    SortKey::DiskRead => SortKey::DiskWrite,
+                    SortKey::DiskWrite => SortKey::Pid,
+                    SortKey::Pid => SortKey::Ppid,
+                    SortKey::Ppid => SortKey::User,
                     SortKey::User => SortKey::Command,
-                    SortKey::Command => SortKey::Cpu,
+                    SortKey::Command => SortKey::Deviation,
+                    SortKey::Deviation => SortKey::Cpu,
                 };
-                self.status_message = Some(format!("Sort: {:?}", self.sort_key));
+                self.status_message = Some(self.sort_label());
                 self.config.sort = Some(match self.sort_key {
                     SortKey::Cpu => "cpu".to_string(),
                     SortKey::Mem => "mem".to_string(),
+                    SortKey::Virt => "virt".to_string(),
+                    SortKey::Shr => "shr".to_string(),
+                    SortKey::DiskRead => "disk_read".to_string(),
+                    SortKey::DiskWrite => "disk_write".to_string(),
                     SortKey::Pid => "pid".to_string(),
+                    SortKey::Ppid => "ppid".to_string(),
                     SortKey::User => "user".to_string(),
                     SortKey::Command => "command".to_string(),
+                    SortKey::Deviation => "deviation".to_string(),
                 });
                 let _ = save_config_file_at(&self.config_path, &self.config);
+                self.process_view_dirty = true;
+                if self.config.sort_jump_to_top {
+                    self.selected = 0;
+                } else if self.config.keep_selection_on_sort {
+                    self.reselect_pid(selected_pid);
+                }
             }
             KeyCode::F(7) => {
                 self.adjust_nice(false);
             }
-            KeyCode::F(8) => {
-                self.adjust_nice(true);
+            KeyCode::F(8) => {
+                self.adjust_nice(true);
+            }
+            // Shift+F9 arrives as F21 on terminals that encode shifted function keys
+            KeyCode::F(9) if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.open_signal_menu();
+            }
+            KeyCode::F(21) => {
+                self.open_signal_menu();
+            }
+            KeyCode::F(9) => {
+                self.kill_selected();
+            }
+            KeyCode::Char('K') => {
+                self.quick_kill_selected();
+            }
+            KeyCode::Char('N') => {
+                self.open_batch_renice();
+            }
+            KeyCode::Char('l') => {
+                self.overlay = Overlay::Events;
+            }
+            KeyCode::Char('t') => {
+                let selected_pid = self.selected_pid();
+                self.tree_mode = !self.tree_mode;
+                self.process_view_dirty = true;
+                self.reselect_pid(selected_pid);
+                self.status_message = Some(if self.tree_mode {
+                    "Tree view: ON (Space folds/unfolds the selected subtree)".to_string()
+                } else {
+                    "Tree view: OFF".to_string()
+                });
+            }
+            KeyCode::Char(' ') if self.tree_mode => {
+                if let Some(pid) = self.selected_pid() {
+                    if !self.collapsed.remove(&pid) {
+                        self.collapsed.insert(pid);
+                    }
+                    self.process_view_dirty = true;
+                }
+            }
+            KeyCode::Char('n') => {
+                // The first read only primes the counters, so rates show 0 until the next tick
+                self.iface_rates = self.monitor.get_network_rates_per_iface();
+                self.overlay = Overlay::Network;
+            }
+            KeyCode::Char('d') => {
+                self.overlay = Overlay::Disks;
+            }
+            KeyCode::Char('b') => {
+                self.system_info =
+                    Some((self.monitor.get_system_info(), self.monitor.get_boot_info()));
+                self.overlay = Overlay::SystemInfo;
+            }
+            KeyCode::Char('i') => {
+                self.cycle_quiet_mode();
+            }
+            KeyCode::Char('c') => {
+                if self.chart_cursor.is_some() {
+                    self.chart_cursor = None;
+                    self.status_message = Some("Chart cursor: OFF".to_string());
+                } else if self.config.show_charts {
+                    self.chart_cursor = Some(0);
+                    self.status_message =
+                        Some("Chart cursor: ←/→ move, c/Esc to leave".to_string());
+                } else {
+                    self.status_message = Some("Charts are hidden (F5 to show)".to_string());
+                }
+            }
+            KeyCode::Left if self.chart_cursor.is_some() => {
+                let max_back = self.history_times.len().saturating_sub(1);
+                self.chart_cursor = self.chart_cursor.map(|n| (n + 1).min(max_back));
+            }
+            KeyCode::Right if self.chart_cursor.is_some() => {
+                self.chart_cursor = self.chart_cursor.map(|n| n.saturating_sub(1));
+            }
+            KeyCode::Esc if overlay_at_entry == Overlay::None && self.chart_cursor.is_some() => {
+                self.chart_cursor = None;
+            }
+            KeyCode::Char('R') => {
+                self.restart_selected();
+            }
+            KeyCode::Char('p') => {
+                self.status_message = Some(match self.plugins.reload_all() {
+                    Ok((n, failed)) if failed.is_empty() => format!("Plugins reloaded ({})", n),
+                    Ok((n, failed)) => format!(
+                        "Plugins reloaded ({}), {} failed: {}",
+                        n,
+                        failed.len(),
+                        failed[0]
+                    ),
+                    Err(e) => format!("Plugin reload failed: {}", e),
+                });
+            }
+            KeyCode::Char('P') => {
+                self.cycle_refresh_profile();
+            }
+            KeyCode::Char('M') => {
+                self.config.manual_refresh = !self.config.manual_refresh;
+                self.status_message = Some(if self.config.manual_refresh {
+                    "Manual refresh: ON (r to refresh, M to resume)".to_string()
+                } else {
+                    "Manual refresh: OFF".to_string()
+                });
+            }
+            KeyCode::Char('r') => {
+                self.refresh_now();
+                self.status_message = Some(format!(
+                    "Refreshed at {}",
+                    chrono::Local::now().format("%H:%M:%S")
+                ));
+            }
+            KeyCode::Char('z') => {
+                self.zen = !self.zen;
+                self.status_message = Some(if self.zen {
+                    "Zen mode: ON (z to restore)".to_string()
+                } else {
+                    "Zen mode: OFF".to_string()
+                });
+            }
+            KeyCode::Enter if overlay_at_entry == Overlay::None => {
+                self.detail_pid = self.selected_pid();
+                if let Some(pid) = self.detail_pid {
+                    self.detail_mem = Some(self.monitor.get_memory_map_summary(pid));
+                    self.detail_sched = Some(self.monitor.get_sched_policy(pid));
+                    self.detail_wchan = self.monitor.get_wchan(pid);
+                    self.overlay = Overlay::Detail;
+                }
+            }
+            KeyCode::F(11) => {
+                self.open_export_menu();
+            }
+            KeyCode::F(12) => {
+                self.show_ai_insights();
+            }
+            KeyCode::Tab => {
+                self.cycle_theme(true);
+            }
+            _ => {}
+        }
+    }
+
+    // Enter/leave power-saving mode; returns true when tick intervals must be rebuilt
+    fn update_idle_state(&mut self) -> bool {
+        let idle = self.config.idle_timeout_secs > 0
+            && self.last_input.elapsed() >= Duration::from_secs(self.config.idle_timeout_secs);
+        if idle == self.idle {
+            return false;
+        }
+        self.idle = idle;
+        self.status_message = Some(if idle {
+            format!(
+                "Idle: refresh slowed to {:.1}s (press any key to resume)",
+                self.config.idle_rate_ms as f64 / 1000.0
+            )
+        } else {
+            "Refresh rate restored".to_string()
+        });
+        true
+    }
+
+    // Start the screensaver once input has been idle long enough; advance it while it shows
+    fn update_screensaver(&mut self) {
+        let due = self.config.screensaver
+            && self.last_input.elapsed() >= Duration::from_secs(self.config.screensaver_after_secs);
+        if !due {
+            return;
+        }
+        if self.screensaver.is_none() {
+            self.screensaver = Some(screensaver::Rain::new());
+            self.rates_changed = true;
+        }
+        let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+        if let Some(rain) = self.screensaver.as_mut() {
+            rain.step(width, height);
+        }
+    }
+
+    fn effective_ui_ms(&self) -> u64 {
+        // Animation needs a smooth frame rate regardless of the configured refresh
+        if self.screensaver.is_some() {
+            return SCREENSAVER_FRAME_MS;
+        }
+        if self.idle {
+            self.config.ui_rate_ms.max(self.config.idle_rate_ms)
+        } else {
+            self.config.ui_rate_ms
+        }
+    }
+
+    fn effective_data_ms(&self) -> u64 {
+        if self.idle {
+            self.config.data_rate_ms.max(self.config.idle_rate_ms)
+        } else {
+            self.config.data_rate_ms
+        }
+    }
+
+    fn effective_process_ms(&self) -> u64 {
+        let ms = self
+            .config
+            .process_rate_ms
+            .unwrap_or(self.config.data_rate_ms);
+        if self.idle {
+            ms.max(self.config.idle_rate_ms)
+        } else {
+            ms
+        }
+    }
+
+    // Spread CPU sub-samples evenly across one UI tick, no tighter than sysinfo allows
+    fn cpu_sample_ms(&self) -> u64 {
+        let min_ms = SystemMonitor::min_cpu_sample_interval().as_millis() as u64;
+        (self.effective_ui_ms() / self.config.cpu_samples.max(1) as u64).max(min_ms)
+    }
+
+    fn collect_processes(&mut self, limit: usize) -> Vec<monitor::ProcessInfo> {
+        self.rebuild_process_view();
+        self.process_view
+            .iter()
+            .take(limit)
+            .filter_map(|&idx| self.processes.get(idx).cloned())
+            .collect()
+    }
+
+    // Replay configured startup commands, then open the startup overlay (kiosk/dashboard setups)
+    fn apply_startup(&mut self) {
+        let mut errors = Vec::new();
+        for cmd in self.config.startup_commands.clone() {
+            let (key, value) = cmd.split_once('=').unwrap_or((cmd.as_str(), ""));
+            let value = value.trim();
+            match key.trim() {
+                "filter" => self.add_filter(value.to_string()),
+                "search" => self.search = value.to_string(),
+                "sort" => match sort_key_from_str(value) {
+                    Some(k) => self.sort_key = k,
+                    None => errors.push(cmd.clone()),
+                },
+                "charts" => self.config.show_charts = !(value == "0" || value == "off"),
+                "theme" => match theme_kind_from_str(value) {
+                    Some(kind) => {
+                        self.theme_kind = kind;
+                        self.theme = Theme::palette(kind);
+                    }
+                    None => errors.push(cmd.clone()),
+                },
+                _ => errors.push(cmd.clone()),
+            }
+        }
+        self.process_view_dirty = true;
+
+        match self.config.startup_overlay.as_deref() {
+            Some("help") => self.overlay = Overlay::Help,
+            Some("setup") => {
+                self.refresh_config_candidates();
+                self.overlay = Overlay::Setup;
+            }
+            Some("search") => {
+                self.input_buffer = self.search.clone();
+                self.overlay = Overlay::Search;
+            }
+            Some("filter") => {
+                self.input_buffer = String::new();
+                self.overlay = Overlay::Filter;
+            }
+            Some("insights") => self.show_ai_insights(),
+            Some(other) => errors.push(format!("startup_overlay={}", other)),
+            None => {}
+        }
+
+        if !errors.is_empty() {
+            self.status_message = Some(format!(
+                "Ignored invalid startup commands: {}",
+                errors.join(", ")
+            ));
+        }
+    }
+
+    fn cycle_quiet_mode(&mut self) {
+        self.config.quiet_mode = match self.config.quiet_mode.as_deref() {
+            None => Some("dim".to_string()),
+            Some("dim") => Some("hide".to_string()),
+            _ => None,
+        };
+        self.status_message = Some(format!(
+            "Quiet processes: {}",
+            self.config.quiet_mode.as_deref().unwrap_or("shown")
+        ));
+        self.process_view_dirty = true;
+        let _ = save_config_file_at(&self.config_path, &self.config);
+    }
+
+    fn add_filter(&mut self, term: String) {
+        let term = term.trim().to_string();
+        if !term.is_empty() && !self.filters.contains(&term) {
+            self.filters.push(term);
+            self.filter_selected = self.filters.len() - 1;
+            self.process_view_dirty = true;
+        }
+    }
+
+    fn remove_filter(&mut self, idx: usize) {
+        let removed = self.filters.remove(idx);
+        self.filter_selected = self
+            .filter_selected
+            .min(self.filters.len().saturating_sub(1));
+        self.process_view_dirty = true;
+        self.clamp_selection();
+        self.status_message = Some(format!("Filter removed: {}", removed));
+    }
+
+    // Re-filter right away so `selected` can't point past a list that just shrank; waiting
+    // for the next frame leaves selected_pid() and navigation working off a stale index
+    fn clamp_selection(&mut self) {
+        self.rebuild_process_view();
+        self.selected = self.selected.min(self.process_view.len().saturating_sub(1));
+    }
+
+    // "Sort: Cpu ↓": the arrow shows the effective direction, not whether it was flipped
+    fn sort_label(&self) -> String {
+        let ascending = matches!(
+            self.sort_key,
+            SortKey::Pid | SortKey::Ppid | SortKey::User | SortKey::Command
+        ) != self.config.sort_reversed;
+        format!(
+            "Sort: {:?} {}",
+            self.sort_key,
+            if ascending { "↑" } else { "↓" }
+        )
+    }
+
+    fn toggle_sort_direction(&mut self) {
+        let selected_pid = self.selected_pid();
+        self.config.sort_reversed = !self.config.sort_reversed;
+        self.status_message = Some(self.sort_label());
+        let _ = save_config_file_at(&self.config_path, &self.config);
+        self.process_view_dirty = true;
+        if self.config.sort_jump_to_top {
+            self.selected = 0;
+        } else if self.config.keep_selection_on_sort {
+            self.reselect_pid(selected_pid);
+        }
+    }
+
+    // Move the cursor to wherever `pid` landed after the view was re-sorted; stays put if it's gone
+    fn reselect_pid(&mut self, pid: Option<u32>) {
+        let Some(pid) = pid else {
+            return;
+        };
+        self.rebuild_process_view();
+        if let Some(idx) = self
+            .process_view
+            .iter()
+            .position(|&pidx| self.processes.get(pidx).is_some_and(|p| p.pid == pid))
+        {
+            self.selected = idx;
+        }
+    }
+
+    fn selected_pid(&mut self) -> Option<u32> {
+        self.rebuild_process_view();
+        if self.process_view.is_empty() {
+            return None;
+        }
+        let idx = self.selected.min(self.process_view.len().saturating_sub(1));
+        let pidx = self.process_view[idx];
+        self.processes.get(pidx).map(|p| p.pid)
+    }
+
+    fn adjust_nice(&mut self, increase: bool) {
+        if let Some(pid) = self.selected_pid() {
+            if !increase {
+                // Guard: going below the configured nice floor needs an explicit yes
+                match self.monitor.get_nice(pid) {
+                    Ok(cur) if cur - 1 < self.config.renice_confirm_below => {
+                        self.pending_action = Some(PendingAction::Renice {
+                            pid,
+                            from: cur,
+                            to: cur - 1,
+                        });
+                        self.overlay = Overlay::Confirm;
+                        return;
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.status_message = Some(format!("Nice change failed: {}", e));
+                        return;
+                    }
+                }
+            }
+            let res = if increase {
+                self.monitor.nice_increase(pid)
+            } else {
+                self.monitor.nice_decrease(pid)
+            };
+            self.status_message = Some(match res {
+                Ok(_) => format!("Nice adjusted for PID {}", pid),
+                Err(e) => format!("Nice change failed: {}", e),
+            });
+        }
+    }
+
+    fn confirm_pending_action(&mut self) {
+        let Some(action) = self.pending_action.take() else {
+            return;
+        };
+        match action {
+            PendingAction::Renice { pid, to, .. } => {
+                self.status_message = Some(match self.monitor.set_nice(pid, to) {
+                    Ok(_) => format!("Nice set to {} for PID {}", to, pid),
+                    Err(e) => format!("Nice change failed: {}", e),
+                });
+            }
+            PendingAction::Restart { pid, args, cwd } => {
+                let preexisting = self.monitor.find_pids_by_args(&args);
+                match self.monitor.kill_process(pid) {
+                    Ok(()) => {
+                        self.pending_restart = Some(PendingRestart {
+                            pid,
+                            args,
+                            cwd,
+                            sent_at: Instant::now(),
+                            exited_at: None,
+                            preexisting,
+                        });
+                        self.status_message = Some(format!("Restart: SIGTERM sent to PID {}", pid));
+                    }
+                    Err(e) => self.status_message = Some(format!("Restart failed: {}", e)),
+                }
+            }
+            PendingAction::Reexec { args, cwd } => {
+                self.status_message = Some(match spawn_detached(&args, cwd.as_deref()) {
+                    Ok(pid) => {
+                        let msg = format!("Restarted {} as PID {}", args[0], pid);
+                        self.log_event(msg.clone());
+                        msg
+                    }
+                    Err(e) => format!("Re-run failed: {}", e),
+                });
+            }
+            PendingAction::ResetConfig { path } => {
+                self.config = AppConfig::default();
+                self.apply_config_runtime();
+                self.status_message = Some(match save_config_file_at(&path, &self.config) {
+                    Ok(()) => format!("Config reset to defaults: {}", path.display()),
+                    Err(e) => format!("Defaults applied, but saving failed: {}", e),
+                });
+            }
+            PendingAction::CreateConfig { path, source } => self.create_config_at(path, source),
+            PendingAction::Kill { pid, .. } => self.terminate(pid),
+            PendingAction::BatchRenice { nice, targets } => {
+                let mut failed = 0;
+                for (pid, command) in &targets {
+                    let outcome = match self.monitor.set_nice(*pid, nice) {
+                        Ok(_) => "ok".to_string(),
+                        Err(e) => {
+                            failed += 1;
+                            e
+                        }
+                    };
+                    self.log_event(format!(
+                        "Renice PID {} ({}) to {}: {}",
+                        pid,
+                        truncate_chars(command, 40),
+                        nice,
+                        outcome
+                    ));
+                }
+                self.status_message = Some(format!(
+                    "Batch renice to {}: {} ok, {} failed (l shows each process)",
+                    nice,
+                    targets.len() - failed,
+                    failed
+                ));
+            }
+        }
+    }
+
+    fn open_batch_renice(&mut self) {
+        if self.batch_terms().is_empty() {
+            self.status_message = Some(
+                "Batch renice applies to the current search/filter: set one first (F3/F4)"
+                    .to_string(),
+            );
+            return;
+        }
+        self.input_buffer.clear();
+        self.overlay = Overlay::BatchNice;
+    }
+
+    fn match_mode(&self) -> MatchMode {
+        MatchMode::from_config(self.config.match_mode.as_deref())
+    }
+
+    // Search plus filter chips, the terms a batch action matches against (AND-ed)
+    fn batch_terms(&self) -> Vec<String> {
+        self.filters
+            .iter()
+            .chain(std::iter::once(&self.search))
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect()
+    }
+
+    // Every process matching all terms, not just the rows that fit on screen; same
+    // matching as the list itself, so regex/fuzzy modes renice what the list shows
+    fn batch_targets(&self) -> Vec<(u32, String)> {
+        let terms = self.batch_terms();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+        let mode = self.match_mode();
+        let matchers: Vec<(&str, TermMatcher)> = terms
+            .iter()
+            .map(|t| (t.as_str(), TermMatcher::new_or_literal(t, mode)))
+            .collect();
+        let mut targets: Vec<(u32, String)> = self
+            .processes
+            .iter()
+            .filter(|p| matches_terms(p, &matchers))
+            .map(|p| (p.pid, p.command.clone()))
+            .collect();
+        targets.sort_by_key(|(pid, _)| *pid);
+        targets
+    }
+
+    fn prepare_batch_renice(&mut self, input: &str) {
+        let nice = match input.trim().parse::<i32>() {
+            Ok(n) if (-20..=19).contains(&n) => n,
+            _ => {
+                self.status_message = Some(format!(
+                    "Invalid nice value {:?} (expected -20..19)",
+                    input.trim()
+                ));
+                return;
+            }
+        };
+        let targets = self.batch_targets();
+        if targets.is_empty() {
+            self.status_message = Some("No processes match the search/filter".to_string());
+            return;
+        }
+        self.pending_action = Some(PendingAction::BatchRenice { nice, targets });
+        self.overlay = Overlay::Confirm;
+    }
+
+    // Ask before bouncing the selected process; its argv is captured now, while it still exists
+    fn restart_selected(&mut self) {
+        let Some(p) = self
+            .selected_pid()
+            .and_then(|pid| self.processes.iter().find(|p| p.pid == pid))
+        else {
+            return;
+        };
+        if p.args.is_empty() {
+            self.status_message = Some(format!(
+                "PID {} has no command line (kernel thread?)",
+                p.pid
+            ));
+            return;
+        }
+        self.pending_action = Some(PendingAction::Restart {
+            pid: p.pid,
+            args: p.args.clone(),
+            cwd: self.monitor.get_process_cwd(p.pid),
+        });
+        self.overlay = Overlay::Confirm;
+    }
+
+    // Follow a restart: wait for exit, give a supervisor time to respawn, else offer to re-run
+    fn poll_pending_restart(&mut self) {
+        let Some(mut pending) = self.pending_restart.take() else {
+            return;
+        };
+        let Some(exited_at) = pending.exited_at else {
+            if self.monitor.is_process_alive(pending.pid) {
+                if pending.sent_at.elapsed() >= Duration::from_millis(self.config.kill_wait_ms) {
+                    self.status_message = Some(format!(
+                        "Restart aborted: PID {} ignored SIGTERM (F9 twice to force)",
+                        pending.pid
+                    ));
+                } else {
+                    self.pending_restart = Some(pending);
+                }
+            } else {
+                pending.exited_at = Some(Instant::now());
+                self.pending_restart = Some(pending);
+            }
+            return;
+        };
+        if let Some(new_pid) = self
+            .monitor
+            .find_pids_by_args(&pending.args)
+            .into_iter()
+            .find(|p| !pending.preexisting.contains(p))
+        {
+            let msg = format!("PID {} was respawned as PID {}", pending.pid, new_pid);
+            self.log_event(msg.clone());
+            self.status_message = Some(msg);
+            return;
+        }
+        if exited_at.elapsed() < RESPAWN_GRACE {
+            self.pending_restart = Some(pending);
+            return;
+        }
+        if self.overlay != Overlay::None {
+            // Don't yank the user out of another dialog; ask once they are back
+            self.pending_restart = Some(pending);
+            return;
+        }
+        self.pending_action = Some(PendingAction::Reexec {
+            args: pending.args,
+            cwd: pending.cwd,
+        });
+        self.overlay = Overlay::Confirm;
+    }
+
+    fn open_signal_menu(&mut self) {
+        self.signal_pid = self.selected_pid();
+        if self.signal_pid.is_some() {
+            self.signal_selected = 0;
+            self.input_buffer.clear();
+            self.overlay = Overlay::Signal;
+        }
+    }
+
+    // A typed name/number wins over the highlighted list entry
+    fn send_selected_signal(&mut self) {
+        let typed = std::mem::take(&mut self.input_buffer);
+        let Some(pid) = self.signal_pid.take() else {
+            return;
+        };
+        let signal = if typed.is_empty() {
+            match SIGNAL_CHOICES.get(self.signal_selected) {
+                Some(&(signal, _)) => signal,
+                None => return,
+            }
+        } else {
+            match parse_signal(&typed) {
+                Ok(signal) => signal,
+                Err(e) => {
+                    self.status_message = Some(e);
+                    return;
+                }
             }
-            KeyCode::F(9) => {
-                self.kill_selected();
+        };
+        self.status_message = Some(match self.monitor.send_signal(pid, signal) {
+            Ok(()) => format!("Sent {} to PID {}", signal.as_str(), pid),
+            Err(e) => format!("{} failed: {}", signal.as_str(), e),
+        });
+    }
+
+    fn kill_selected(&mut self) {
+        let Some(pid) = self.selected_pid() else {
+            return;
+        };
+
+        // Second F9 on a process that survived SIGTERM escalates to SIGKILL
+        if let Some(pending) = self.pending_kill
+            && pending.pid == pid
+            && pending.survived
+            && !pending.escalated
+        {
+            match self.monitor.force_kill_process(pid) {
+                Ok(_) => {
+                    self.pending_kill = Some(PendingKill {
+                        pid,
+                        sent_at: Instant::now(),
+                        escalated: true,
+                        survived: false,
+                    });
+                    self.status_message = Some(format!("Sent SIGKILL to PID {}", pid));
+                }
+                Err(e) => {
+                    self.pending_kill = None;
+                    self.status_message = Some(format!("Kill failed: {}", e));
+                }
             }
-            KeyCode::F(11) => {
-                self.export_snapshot();
+            return;
+        }
+
+        if self.config.confirm_kill {
+            // Capture the target now; the list may re-sort while the dialog is open
+            if let Some(p) = self.processes.iter().find(|p| p.pid == pid) {
+                self.pending_action = Some(PendingAction::Kill {
+                    pid,
+                    user: p.user.clone(),
+                    command: p.command.clone(),
+                });
+                self.overlay = Overlay::Confirm;
             }
-            KeyCode::F(12) => {
-                self.show_ai_insights();
+            return;
+        }
+        self.terminate(pid);
+    }
+
+    // Opt-in escape hatch for stuck processes: straight to SIGKILL, never confirmed
+    fn quick_kill_selected(&mut self) {
+        if !self.config.quick_kill {
+            self.status_message =
+                Some("Quick kill is off (set quick_kill = true to enable K)".to_string());
+            return;
+        }
+        let Some(pid) = self.selected_pid() else {
+            return;
+        };
+        match self.monitor.force_kill_process(pid) {
+            Ok(_) => {
+                self.pending_kill = Some(PendingKill {
+                    pid,
+                    sent_at: Instant::now(),
+                    escalated: true,
+                    survived: false,
+                });
+                self.status_message = Some(format!("Quick kill: sent SIGKILL to PID {}", pid));
             }
-            KeyCode::Tab => {
-                self.cycle_theme(true);
+            Err(e) => {
+                self.status_message = Some(format!("Kill failed: {}", e));
             }
-            _ => {}
         }
     }
 
-    fn collect_processes(&mut self, limit: usize) -> Vec<monitor::ProcessInfo> {
-        self.rebuild_process_view();
-        self.process_view
-            .iter()
-            .take(limit)
-            .filter_map(|&idx| self.processes.get(idx).cloned())
-            .collect()
+    // SIGTERM, then watch for the exit so a second F9 can escalate
+    fn terminate(&mut self, pid: u32) {
+        match self.monitor.kill_process(pid) {
+            Ok(_) => {
+                self.status_message = Some(format!("Sent SIGTERM to PID {}", pid));
+                self.pending_kill = if self.config.kill_wait_ms > 0 {
+                    Some(PendingKill {
+                        pid,
+                        sent_at: Instant::now(),
+                        escalated: false,
+                        survived: false,
+                    })
+                } else {
+                    None
+                };
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Kill failed: {}", e));
+            }
+        }
     }
 
-    fn selected_pid(&mut self) -> Option<u32> {
-        self.rebuild_process_view();
-        if self.process_view.is_empty() {
-            return None;
+    fn poll_pending_kill(&mut self) {
+        let Some(mut pending) = self.pending_kill else {
+            return;
+        };
+        let signal = if pending.escalated {
+            "SIGKILL"
+        } else {
+            "SIGTERM"
+        };
+        let elapsed = pending.sent_at.elapsed();
+
+        if !self.monitor.is_process_alive(pending.pid) {
+            let msg = format!(
+                "PID {} terminated after {:.1}s ({})",
+                pending.pid,
+                elapsed.as_secs_f64(),
+                signal
+            );
+            self.log_event(msg.clone());
+            self.status_message = Some(msg);
+            self.pending_kill = None;
+            return;
         }
-        let idx = self.selected.min(self.process_view.len().saturating_sub(1));
-        let pidx = self.process_view[idx];
-        self.processes.get(pidx).map(|p| p.pid)
-    }
 
-    fn adjust_nice(&mut self, increase: bool) {
-        if let Some(pid) = self.selected_pid() {
-            let res = if increase {
-                self.monitor.nice_increase(pid)
-            } else {
-                self.monitor.nice_decrease(pid)
-            };
-            self.status_message = Some(match res {
-                Ok(_) => format!("Nice adjusted for PID {}", pid),
-                Err(e) => format!("Nice change failed: {}", e),
-            });
+        if pending.survived || elapsed < Duration::from_millis(self.config.kill_wait_ms) {
+            return;
         }
-    }
 
-    fn kill_selected(&mut self) {
-        if let Some(pid) = self.selected_pid() {
-            let res = self.monitor.kill_process(pid);
-            self.status_message = Some(match res {
-                Ok(_) => format!("Sent SIGTERM to PID {}", pid),
-                Err(e) => format!("Kill failed: {}", e),
-            });
+        if pending.escalated {
+            self.status_message = Some(format!(
+                "PID {} still alive after SIGKILL (uninterruptible?)",
+                pending.pid
+            ));
+            self.pending_kill = None;
+        } else {
+            pending.survived = true;
+            self.pending_kill = Some(pending);
+            self.status_message = Some(format!(
+                "PID {} survived SIGTERM for {:.1}s - press F9 again to send SIGKILL",
+                pending.pid,
+                elapsed.as_secs_f64()
+            ));
         }
     }
 }
 
 async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
     // Use tokio intervals to decouple UI/data/input and keep CPU low
-    let mut ui_tick = tokio::time::interval(Duration::from_millis(app.config.ui_rate_ms));
-    ui_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
-    let mut data_tick = tokio::time::interval(Duration::from_millis(app.config.data_rate_ms));
-    data_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
-    let mut input_tick = tokio::time::interval(Duration::from_millis(25));
-    input_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut ui_tick = make_tick(app.effective_ui_ms());
+    let mut data_tick = make_tick(app.effective_data_ms());
+    let mut process_tick = make_tick(app.effective_process_ms());
+    let mut input_tick = make_tick(25);
+    let mut sample_tick = make_tick(app.cpu_sample_ms());
+    // Monitoring plugins collect on their own task and report back here
+    let (metrics_tx, mut metrics_rx) = tokio::sync::mpsc::channel(1);
+    // Export writes run on blocking tasks and report their result here
+    let (export_tx, mut export_rx) = tokio::sync::mpsc::channel(1);
 
     app.update_slow();
+    app.update_processes();
     app.update_fast();
+    app.apply_startup();
     app.rebuild_process_view();
 
     loop {
         tokio::select! {
             _ = ui_tick.tick() => {
-                app.update_fast();
+                app.poll_pending_kill();
+                app.poll_pending_restart();
+                app.apply_auto_theme();
+                app.update_screensaver();
+                if !app.config.manual_refresh && app.screensaver.is_none() {
+                    app.update_fast();
+                }
                 app.rebuild_process_view();
                 terminal
                     .draw(|f| ui(f, &app))
                     .map_err(|e| io::Error::other(e.to_string()))?;
             },
-            _ = data_tick.tick() => {
+            _ = data_tick.tick(), if !app.config.manual_refresh => {
                 app.update_slow();
+                let _ = app.plugins.update_plugins(&app.last_snapshot);
+                if app.plugins.has_monitoring_plugins() && !app.plugin_metrics_pending {
+                    app.plugin_metrics_pending = true;
+                    let job = app.plugins.metrics_collector();
+                    let tx = metrics_tx.clone();
+                    tokio::spawn(async move {
+                        let _ = tx.send(job.await).await;
+                    });
+                }
+            },
+            _ = process_tick.tick(), if !app.config.manual_refresh => {
+                app.update_processes();
+            },
+            Some((metrics, errors)) = metrics_rx.recv() => {
+                app.set_plugin_metrics(metrics, errors);
+            },
+            Some(result) = export_rx.recv() => {
+                app.finish_export(result);
+            },
+            _ = sample_tick.tick(), if app.config.cpu_samples > 1 && !app.config.manual_refresh => {
+                app.monitor.sample_cpu();
             },
             _ = input_tick.tick() => {
                 while crossterm::event::poll(Duration::from_millis(0))? {
-                    if let Event::Key(key) = event::read()? {
-                        app.handle_key(key);
+                    match event::read()? {
+                        Event::Key(key) => app.handle_key(key),
+                        Event::Mouse(mouse) => app.handle_mouse(mouse),
+                        _ => {}
                     }
                 }
             },
@@ -1040,25 +4300,75 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Re
         if app.should_quit {
             return Ok(());
         }
+
+        if let Some(job) = app.export_job.take() {
+            let tx = export_tx.clone();
+            tokio::spawn(async move {
+                let result = tokio::task::spawn_blocking(job)
+                    .await
+                    .unwrap_or_else(|e| Err(format!("export task failed: {}", e)));
+                let _ = tx.send(result).await;
+            });
+        }
+
+        // Restart the intervals when entering/leaving idle mode (or rates changed) so they apply now
+        let idle_changed = app.update_idle_state();
+        if idle_changed || std::mem::take(&mut app.rates_changed) {
+            ui_tick = make_tick(app.effective_ui_ms());
+            data_tick = make_tick(app.effective_data_ms());
+            process_tick = make_tick(app.effective_process_ms());
+            sample_tick = make_tick(app.cpu_sample_ms());
+        }
     }
 }
 
+fn make_tick(ms: u64) -> tokio::time::Interval {
+    let mut tick = tokio::time::interval(Duration::from_millis(ms.max(1)));
+    tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    tick
+}
+
 fn ui(f: &mut Frame, app: &App) {
+    if let Some(rain) = &app.screensaver {
+        rain.render(
+            f,
+            f.area(),
+            app.theme.accent,
+            app.theme.bg,
+            app.theme_kind == ThemeKind::Matrix && app.braille_charts,
+        );
+        return;
+    }
     // Adaptive layout depending on charts toggle - more compact like htop
     let cpu_rows = app.monitor.get_cpu_count().div_ceil(2) as u16;
+    // Zen mode drops the header, outer margin and table chrome to give rows back to the list
+    let header_height = if app.zen { 0 } else { 4 };
+    let margin = if app.zen { 0 } else { 1 };
+    let show_plugin_metrics = !app.plugin_metrics.is_empty();
+    // Short terminals keep room for the process table (borders, header, a few rows):
+    // the charts are dropped first, then the CPU bars are cut off
+    const MIN_TABLE_HEIGHT: u16 = 6;
+    let available = f.area().height.saturating_sub(2 * margin);
+    let fixed = header_height + 4 + if show_plugin_metrics { 3 } else { 0 };
+    let cpu_height = (5 + cpu_rows).min(available.saturating_sub(fixed + MIN_TABLE_HEIGHT));
+    let show_charts =
+        app.config.show_charts && available >= fixed + cpu_height + 12 + MIN_TABLE_HEIGHT;
     let mut vertical = vec![
-        Constraint::Length(3),            // Header
-        Constraint::Length(5 + cpu_rows), // CPU bars (htop-style)
-        Constraint::Length(4),            // Memory, Swap, Tasks
+        Constraint::Length(header_height), // Header + status line
+        Constraint::Length(cpu_height),    // CPU bars (htop-style)
+        Constraint::Length(4),             // Memory, Swap, Tasks
     ];
-    if app.config.show_charts {
+    if show_charts {
         vertical.push(Constraint::Length(12)); // Charts
     }
+    if show_plugin_metrics {
+        vertical.push(Constraint::Length(3)); // Plugin metrics
+    }
     vertical.push(Constraint::Min(0)); // Process list
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .margin(1)
+        .margin(margin)
         .constraints(vertical)
         .split(f.area());
 
@@ -1072,8 +4382,70 @@ fn ui(f: &mut Frame, app: &App) {
         uptime_secs % 60
     );
     let process_count = app.monitor.get_process_count();
+    // Second header line: header_fields in order; unknown names and metrics this machine
+    // can't provide are left out
+    let value_style = Style::default()
+        .fg(app.theme.fg)
+        .add_modifier(Modifier::BOLD);
+    let mut metric_spans = Vec::new();
+    for field in &app.config.header_fields {
+        let spans = match field.as_str() {
+            "tasks" => vec![
+                Span::styled("Tasks: ", Style::default().fg(app.theme.accent)),
+                Span::styled(format!("{}", process_count), value_style),
+                Span::raw(" thr; "),
+                Span::styled("1", Style::default().fg(theme::get_run_color(&app.theme))),
+                Span::raw(" running"),
+            ],
+            "load" => vec![
+                Span::styled("Load average: ", Style::default().fg(app.theme.accent)),
+                Span::styled(
+                    format!("{:.2} {:.2} {:.2}", load1, load5, load15),
+                    value_style,
+                ),
+            ],
+            "uptime" => vec![
+                Span::styled("Uptime: ", Style::default().fg(app.theme.accent)),
+                Span::styled(uptime_str.clone(), value_style),
+            ],
+            "fds" => match app.open_files {
+                Some((open, max)) => vec![
+                    Span::styled("FDs: ", Style::default().fg(app.theme.accent)),
+                    Span::styled(format!("{}", open), value_style),
+                    // The default limit is effectively unbounded; only show a real one
+                    Span::raw(if max < u32::MAX as u64 {
+                        format!("/{}", max)
+                    } else {
+                        String::new()
+                    }),
+                ],
+                None => continue,
+            },
+            "net" => {
+                let rx = app.net_rx_history.back().copied().unwrap_or(0.0);
+                let tx = app.net_tx_history.back().copied().unwrap_or(0.0);
+                let rate = |v: f64| humansize::format_size(v.max(0.0) as u64, humansize::DECIMAL);
+                vec![
+                    Span::styled("Net: ", Style::default().fg(app.theme.accent)),
+                    Span::styled(format!("↓{}/s ↑{}/s", rate(rx), rate(tx)), value_style),
+                ]
+            }
+            "temp" => match app.cpu_temp {
+                Some(temp) => vec![
+                    Span::styled("Temp: ", Style::default().fg(app.theme.accent)),
+                    Span::styled(format!("{:.0}°C", temp), value_style),
+                ],
+                None => continue,
+            },
+            _ => continue,
+        };
+        if !metric_spans.is_empty() {
+            metric_spans.push(Span::raw("   "));
+        }
+        metric_spans.extend(spans);
+    }
 
-    let header_text = vec![
+    let mut header_text = vec![
         Line::from(vec![
             Span::styled(
                 "Lyvoxa",
@@ -1082,36 +4454,82 @@ fn ui(f: &mut Frame, app: &App) {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" - F1 Help | F5 Charts | F11 Export | F12 Insights | Tab Themes | F10 Quit"),
-        ]),
-        Line::from(vec![
-            Span::styled("Tasks: ".to_string(), Style::default().fg(app.theme.accent)),
-            Span::styled(
-                format!("{}", process_count),
-                Style::default()
-                    .fg(app.theme.fg)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(" thr; "),
-            Span::styled("1", Style::default().fg(theme::get_run_color(&app.theme))),
-            Span::raw(" running"),
-            Span::raw("   "),
-            Span::styled("Load average: ", Style::default().fg(app.theme.accent)),
-            Span::styled(
-                format!("{:.2} {:.2} {:.2}", load1, load5, load15),
-                Style::default()
-                    .fg(app.theme.fg)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw("   "),
-            Span::styled("Uptime: ", Style::default().fg(app.theme.accent)),
             Span::styled(
-                uptime_str,
+                if app.unprivileged {
+                    "  (unprivileged)"
+                } else {
+                    ""
+                },
                 Style::default()
-                    .fg(app.theme.fg)
-                    .add_modifier(Modifier::BOLD),
+                    .fg(app.theme.warn)
+                    .add_modifier(Modifier::DIM),
             ),
         ]),
+        Line::from(metric_spans),
     ];
+    // Status line: active filter chips, then the latest status message
+    // (insights reuse status_message for their body, so skip it while shown)
+    let mut status_spans = Vec::new();
+    if !app.filters.is_empty() {
+        status_spans.push(Span::styled(
+            "Filters: ",
+            Style::default().fg(app.theme.accent),
+        ));
+        for term in &app.filters {
+            status_spans.push(Span::styled(
+                format!("[{} x]", term),
+                Style::default().fg(app.theme.fg).bg(app.theme.selection_bg),
+            ));
+            status_spans.push(Span::raw(" "));
+        }
+        status_spans.push(Span::raw("  "));
+    }
+    // --cgroup: the process list is the group, so total it up here
+    if let Some(scope) = app.monitor.cgroup_scope() {
+        let cpu: f32 = app.processes.iter().map(|p| p.cpu_usage).sum();
+        let mem: u64 = app.processes.iter().map(|p| p.mem_bytes).sum();
+        status_spans.push(Span::styled(
+            format!(
+                "cgroup {}: {} procs  CPU {:.1}%  Mem {}",
+                scope,
+                app.processes.len(),
+                cpu,
+                humansize::format_size(mem, humansize::DECIMAL)
+            ),
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+        status_spans.push(Span::raw("  "));
+    }
+    if app.config.manual_refresh {
+        status_spans.push(Span::styled(
+            "MANUAL (r)",
+            Style::default()
+                .fg(app.theme.warn)
+                .add_modifier(Modifier::BOLD),
+        ));
+        status_spans.push(Span::raw("  "));
+    }
+    if !app.env_overrides.is_empty() {
+        let fields: Vec<&str> = app.env_overrides.iter().map(|o| o.field).collect();
+        status_spans.push(Span::styled(
+            format!("env*: {}", fields.join(",")),
+            Style::default().fg(app.theme.warn),
+        ));
+        status_spans.push(Span::raw("  "));
+    }
+    if app.overlay != Overlay::Insights
+        && let Some(msg) = app.status_message.as_deref().and_then(|m| m.lines().next())
+    {
+        status_spans.push(Span::styled(
+            msg.to_string(),
+            Style::default().fg(app.theme.accent),
+        ));
+    }
+    if !status_spans.is_empty() {
+        header_text.push(Line::from(status_spans));
+    }
     let header =
         Paragraph::new(header_text).style(Style::default().fg(app.theme.fg).bg(app.theme.bg));
     f.render_widget(header, chunks[0]);
@@ -1119,44 +4537,86 @@ fn ui(f: &mut Frame, app: &App) {
     // CPU bars - htop style with colored █ characters
     let per_core = app.monitor.get_cpu_usage_per_core();
     let cpu_count = per_core.len();
+    // Trailing 'T' on cores whose thermal throttle counter moved since the last refresh
+    let show_throttle = app.monitor.has_throttle_info();
+    let throttle_flag = |core: usize| -> Span<'static> {
+        if app.monitor.is_core_throttled(core) {
+            Span::styled(
+                "T",
+                Style::default()
+                    .fg(app.theme.bar_high)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            Span::raw(" ")
+        }
+    };
     let halfway = cpu_count.div_ceil(2);
+    // (logical CPU, label) in display order; siblings end up next to each other
+    let smt_groups = app.monitor.get_smt_groups();
+    let core_order: Vec<(usize, String)> =
+        if app.config.cpu_group_siblings && !smt_groups.is_empty() {
+            smt_groups
+                .iter()
+                .enumerate()
+                .flat_map(|(core, group)| {
+                    group.iter().enumerate().map(move |(k, &cpu)| {
+                        (cpu, format!("{}{}", core, (b'a' + k as u8) as char))
+                    })
+                })
+                .filter(|(cpu, _)| *cpu < cpu_count)
+                .collect()
+        } else {
+            (0..cpu_count).map(|cpu| (cpu, cpu.to_string())).collect()
+        };
 
     let mut cpu_lines = Vec::new();
     let bar_width = 25; // Width of each progress bar
+    // Right-aligned to the width of "100" plus the configured decimals
+    let decimals = app.config.percent_decimals.min(2);
+    let pct = |v: f64| {
+        let width = if decimals == 0 { 3 } else { 4 + decimals };
+        format!("{:>width$.decimals$}%", v)
+    };
 
     for row in 0..halfway {
         let mut line_spans = Vec::new();
 
         // Left column
-        if row < cpu_count {
-            let val = per_core[row];
+        if let Some((cpu, label)) = core_order.get(row) {
+            let val = per_core[*cpu];
             line_spans.push(Span::styled(
-                format!("{:>3}[", row),
+                format!("{:>3}[", label),
                 Style::default().fg(app.theme.cpu_label),
             ));
             line_spans.extend(make_colored_bar(val, bar_width, &app.theme));
             line_spans.push(Span::styled(
-                format!("]{:>5.1}%", val),
-                Style::default().fg(app.theme.cpu_label),
+                format!("]{}", pct(val as f64)),
+                Style::default().fg(app.severity_color(val as f64, app.theme.cpu_label)),
             ));
+            if show_throttle {
+                line_spans.push(throttle_flag(*cpu));
+            }
         }
 
         // Spacing
         line_spans.push(Span::raw("  "));
 
         // Right column
-        let right_idx = row + halfway;
-        if right_idx < cpu_count {
-            let val = per_core[right_idx];
+        if let Some((cpu, label)) = core_order.get(row + halfway) {
+            let val = per_core[*cpu];
             line_spans.push(Span::styled(
-                format!("{:>3}[", right_idx),
+                format!("{:>3}[", label),
                 Style::default().fg(app.theme.cpu_label),
             ));
             line_spans.extend(make_colored_bar(val, bar_width, &app.theme));
             line_spans.push(Span::styled(
-                format!("]{:>5.1}%", val),
-                Style::default().fg(app.theme.cpu_label),
+                format!("]{}", pct(val as f64)),
+                Style::default().fg(app.severity_color(val as f64, app.theme.cpu_label)),
             ));
+            if show_throttle {
+                line_spans.push(throttle_flag(*cpu));
+            }
         }
 
         cpu_lines.push(Line::from(line_spans));
@@ -1187,69 +4647,196 @@ fn ui(f: &mut Frame, app: &App) {
     mem_line.extend(make_colored_bar(memory_usage as f32, bar_width, &app.theme));
     mem_line.push(Span::styled(
         format!(
-            "]{:>5.1}% {}/{}",
-            memory_usage,
-            humansize::format_size(used_mem * 1024, humansize::DECIMAL),
-            humansize::format_size(total_mem * 1024, humansize::DECIMAL)
+            "]{} {}/{}",
+            pct(memory_usage),
+            humansize::format_size(used_mem, humansize::DECIMAL),
+            humansize::format_size(total_mem, humansize::DECIMAL)
         ),
-        Style::default().fg(app.theme.mem_label),
+        Style::default().fg(app.severity_color(memory_usage, app.theme.mem_label)),
     ));
     mem_lines.push(Line::from(mem_line));
 
-    // Swap bar
+    // Swap bar; a muted placeholder when there is no swap at all
     let mut swap_line = Vec::new();
     swap_line.push(Span::styled(
         "Swp[",
         Style::default().fg(app.theme.swap_label),
     ));
-    swap_line.extend(make_colored_bar(swap_usage as f32, bar_width, &app.theme));
-    swap_line.push(Span::styled(
-        format!(
-            "]{:>5.1}% {}/{}",
-            swap_usage,
-            humansize::format_size(used_swap * 1024, humansize::DECIMAL),
-            humansize::format_size(total_swap * 1024, humansize::DECIMAL)
-        ),
-        Style::default().fg(app.theme.swap_label),
-    ));
+    if total_swap == 0 {
+        swap_line.push(Span::styled(
+            format!("{:^width$}", "no swap", width = bar_width),
+            Style::default()
+                .fg(app.theme.bar_empty)
+                .add_modifier(Modifier::DIM),
+        ));
+        swap_line.push(Span::styled("]", Style::default().fg(app.theme.swap_label)));
+    } else {
+        swap_line.extend(make_colored_bar(swap_usage as f32, bar_width, &app.theme));
+        swap_line.push(Span::styled(
+            format!(
+                "]{} {}/{}",
+                pct(swap_usage),
+                humansize::format_size(used_swap, humansize::DECIMAL),
+                humansize::format_size(total_swap, humansize::DECIMAL)
+            ),
+            Style::default().fg(app.theme.swap_label),
+        ));
+    }
     mem_lines.push(Line::from(swap_line));
 
+    // CPU temperature on the same 0-100 scale, only where a thermal sensor exists
+    if let Some(temp) = app.cpu_temp {
+        let color = if temp > 80.0 {
+            app.theme.bar_high
+        } else {
+            app.theme.cpu_label
+        };
+        let mut temp_line = vec![Span::styled(
+            "Tmp[",
+            Style::default().fg(app.theme.cpu_label),
+        )];
+        temp_line.extend(make_colored_bar(
+            temp.clamp(0.0, 100.0),
+            bar_width,
+            &app.theme,
+        ));
+        temp_line.push(Span::styled(
+            format!("] {:.1}°C", temp),
+            Style::default().fg(color),
+        ));
+        mem_lines.push(Line::from(temp_line));
+    }
+
+    // Commit charge: memory promised to processes, touched or not; >100% means overcommitted
+    if let Some(commit) = app.monitor.get_commit_info() {
+        let ratio = commit.ratio_percent();
+        let color = if ratio >= 150.0 {
+            app.theme.bar_high
+        } else if ratio >= 100.0 {
+            app.theme.warn
+        } else {
+            app.theme.swap_label
+        };
+        mem_lines.push(Line::from(vec![
+            Span::styled("Commit: ", Style::default().fg(app.theme.swap_label)),
+            Span::styled(
+                format!(
+                    "{} of {} limit ({}){}",
+                    humansize::format_size(commit.committed_kb * 1024, humansize::DECIMAL),
+                    humansize::format_size(commit.limit_kb * 1024, humansize::DECIMAL),
+                    pct(ratio).trim_start(),
+                    if ratio >= 100.0 { " overcommitted" } else { "" }
+                ),
+                Style::default().fg(color),
+            ),
+        ]));
+    }
+
     let mem_widget =
         Paragraph::new(mem_lines).style(Style::default().fg(app.theme.fg).bg(app.theme.bg));
     f.render_widget(mem_widget, chunks[2]);
 
-    // Charts layout (CPU, Memory, Network)
-    if app.config.show_charts {
-        let chart_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
+    // Charts layout (CPU, Memory, Network, optionally Processes)
+    if show_charts {
+        let span = app.history_span_secs();
+        let cursor = app.chart_cursor_point();
+        // Vertical line at the cursor, spanning the chart's y range
+        let cursor_line = |y_max: f64| -> Vec<(f64, f64)> {
+            cursor.map_or_else(Vec::new, |(_, x)| vec![(x, 0.0), (x, y_max)])
+        };
+        // Value under the cursor, plus how long ago that sample was taken
+        let cursor_age = cursor.map(|(idx, _)| app.chart_cursor_age_secs(idx));
+        let cursor_value =
+            |values: &VecDeque<f64>| cursor.and_then(|(idx, _)| values.get(idx).copied());
+        let pct_label = |values: &VecDeque<f64>| match (cursor_age, cursor_value(values)) {
+            (Some(age), Some(v)) => format!(" [-{:.0}s: {}]", age, pct(v).trim_start()),
+            _ => String::new(),
+        };
+        let rate = |v: f64| humansize::format_size(v.max(0.0) as u64, humansize::DECIMAL);
+        // ratatui has no reference lines, so the redline is a two-point dataset across the chart
+        let redline = app.config.chart_redline_percent;
+        let redline_data: Vec<(f64, f64)> = if redline > 0.0 && redline < 100.0 {
+            vec![(0.0, redline), (span, redline)]
+        } else {
+            Vec::new()
+        };
+        let marker = app
+            .config
+            .chart_marker
+            .as_deref()
+            .and_then(chart_marker_from_str)
+            .unwrap_or(if app.braille_charts {
+                symbols::Marker::Braille
+            } else {
+                symbols::Marker::Dot
+            });
+        // Lines drawn with Dot/Block markers are coarse, so only default to them with Braille
+        let graph_type = match app.config.chart_style.as_deref() {
+            Some("line") => GraphType::Line,
+            Some("scatter") => GraphType::Scatter,
+            _ if app.braille_charts => GraphType::Line,
+            _ => GraphType::Scatter,
+        };
+        let chart_constraints: &[Constraint] = if app.config.show_process_chart {
+            &[Constraint::Percentage(25); 4]
+        } else {
+            &[
                 Constraint::Percentage(34),
                 Constraint::Percentage(33),
                 Constraint::Percentage(33),
-            ])
+            ]
+        };
+        let chart_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(chart_constraints)
             .split(chunks[3]);
 
         // CPU chart - only render if we have significant data
         if app.cpu_history.len() > 5 {
             let cpu_data: Vec<(f64, f64)> = app
-                .cpu_history
-                .iter()
-                .enumerate()
+                .timed_series(&app.cpu_history)
+                .into_iter()
                 .step_by(2)
-                .map(|(i, &cpu)| (i as f64, cpu))
                 .collect();
 
-            let datasets = vec![
+            let cpu_cursor = cursor_line(100.0);
+            let mut datasets = vec![
                 Dataset::default()
                     .name("CPU %")
-                    .marker(symbols::Marker::Dot)
-                    .style(Style::default().fg(Color::Yellow))
+                    .marker(marker)
+                    .graph_type(graph_type)
+                    .style(Style::default().fg(app.severity_color(
+                        app.cpu_history.back().copied().unwrap_or(0.0),
+                        app.theme.cpu,
+                    )))
                     .data(&cpu_data),
             ];
+            if !redline_data.is_empty() {
+                // Inserted first so the history is drawn over it
+                datasets.insert(
+                    0,
+                    Dataset::default()
+                        .graph_type(GraphType::Line)
+                        .style(Style::default().fg(app.theme.warn))
+                        .data(&redline_data),
+                );
+            }
+            if !cpu_cursor.is_empty() {
+                datasets.push(
+                    Dataset::default()
+                        .graph_type(GraphType::Line)
+                        .style(Style::default().fg(app.theme.fg))
+                        .data(&cpu_cursor),
+                );
+            }
 
             let cpu_chart = Chart::new(datasets)
-                .block(Block::default().title("CPU History").borders(Borders::ALL))
-                .x_axis(Axis::default().title("Time").bounds([0.0, 120.0]))
+                .block(
+                    Block::default()
+                        .title(format!("CPU History{}", pct_label(&app.cpu_history)))
+                        .borders(Borders::ALL),
+                )
+                .x_axis(Axis::default().title("Time (s)").bounds([0.0, span]))
                 .y_axis(Axis::default().title("Usage %").bounds([0.0, 100.0]));
             f.render_widget(cpu_chart, chart_chunks[0]);
         }
@@ -1257,55 +4844,82 @@ fn ui(f: &mut Frame, app: &App) {
         // Memory chart - only render if we have significant data
         if app.memory_history.len() > 5 {
             let mem_data: Vec<(f64, f64)> = app
-                .memory_history
-                .iter()
-                .enumerate()
+                .timed_series(&app.memory_history)
+                .into_iter()
                 .step_by(2)
-                .map(|(i, &mem)| (i as f64, mem))
                 .collect();
 
-            let datasets = vec![
+            let mem_cursor = cursor_line(100.0);
+            let mut datasets = vec![
                 Dataset::default()
                     .name("Memory %")
-                    .marker(symbols::Marker::Dot)
-                    .style(Style::default().fg(Color::Green))
+                    .marker(marker)
+                    .graph_type(graph_type)
+                    .style(Style::default().fg(app.severity_color(
+                        app.memory_history.back().copied().unwrap_or(0.0),
+                        app.theme.mem,
+                    )))
                     .data(&mem_data),
             ];
+            let swap_data: Vec<(f64, f64)> = app
+                .timed_series(&app.swap_history)
+                .into_iter()
+                .step_by(2)
+                .collect();
+            if app.config.chart_swap && app.monitor.get_swap_info().1 > 0 {
+                datasets.push(
+                    Dataset::default()
+                        .name("Swap %")
+                        .marker(marker)
+                        .graph_type(graph_type)
+                        .style(Style::default().fg(app.theme.swap))
+                        .data(&swap_data),
+                );
+            }
+            if !redline_data.is_empty() {
+                datasets.insert(
+                    0,
+                    Dataset::default()
+                        .graph_type(GraphType::Line)
+                        .style(Style::default().fg(app.theme.warn))
+                        .data(&redline_data),
+                );
+            }
+            if !mem_cursor.is_empty() {
+                datasets.push(
+                    Dataset::default()
+                        .graph_type(GraphType::Line)
+                        .style(Style::default().fg(app.theme.fg))
+                        .data(&mem_cursor),
+                );
+            }
 
             let memory_chart = Chart::new(datasets)
                 .block(
                     Block::default()
-                        .title("Memory History")
+                        .title(format!("Memory History{}", pct_label(&app.memory_history)))
                         .borders(Borders::ALL),
                 )
-                .x_axis(Axis::default().title("Time").bounds([0.0, 120.0]))
+                .x_axis(Axis::default().title("Time (s)").bounds([0.0, span]))
                 .y_axis(Axis::default().title("Usage %").bounds([0.0, 100.0]));
             f.render_widget(memory_chart, chart_chunks[1]);
         }
 
         // Network chart (RX/TX bytes/sec)
         if !app.net_rx_history.is_empty() && !app.net_tx_history.is_empty() {
-            let rx_data: Vec<(f64, f64)> = app
-                .net_rx_history
-                .iter()
-                .enumerate()
-                .map(|(i, &v)| (i as f64, v))
-                .collect();
-            let tx_data: Vec<(f64, f64)> = app
-                .net_tx_history
-                .iter()
-                .enumerate()
-                .map(|(i, &v)| (i as f64, v))
-                .collect();
-            let datasets = vec![
+            let rx_data = app.timed_series(&app.net_rx_history);
+            let tx_data = app.timed_series(&app.net_tx_history);
+            let mut datasets = vec![
                 Dataset::default()
                     .name("RX B/s")
-                    .marker(symbols::Marker::Dot)
+                    .marker(marker)
+                    .graph_type(graph_type)
                     .style(Style::default().fg(app.theme.net_rx))
                     .data(&rx_data),
                 Dataset::default()
                     .name("TX B/s")
-                    .marker(symbols::Marker::Dot)
+                    .marker(marker)
+                    .graph_type(graph_type)
                     .style(Style::default().fg(app.theme.net_tx))
                     .data(&tx_data),
             ];
@@ -1315,9 +4929,31 @@ fn ui(f: &mut Frame, app: &App) {
                 .chain(app.net_tx_history.iter())
                 .cloned()
                 .fold(1.0_f64, |m, v| m.max(v));
+            let net_cursor = cursor_line(max_val * 1.2);
+            if !net_cursor.is_empty() {
+                datasets.push(
+                    Dataset::default()
+                        .graph_type(GraphType::Line)
+                        .style(Style::default().fg(app.theme.fg))
+                        .data(&net_cursor),
+                );
+            }
+            let net_title = match (
+                cursor_age,
+                cursor_value(&app.net_rx_history),
+                cursor_value(&app.net_tx_history),
+            ) {
+                (Some(age), Some(rx), Some(tx)) => format!(
+                    "Network [-{:.0}s: RX {}/s TX {}/s]",
+                    age,
+                    rate(rx),
+                    rate(tx)
+                ),
+                _ => "Network B/s".to_string(),
+            };
             let net_chart = Chart::new(datasets)
-                .block(Block::default().title("Network B/s").borders(Borders::ALL))
-                .x_axis(Axis::default().title("Time").bounds([0.0, 120.0]))
+                .block(Block::default().title(net_title).borders(Borders::ALL))
+                .x_axis(Axis::default().title("Time (s)").bounds([0.0, span]))
                 .y_axis(
                     Axis::default()
                         .title("Bytes/s")
@@ -1325,10 +4961,144 @@ fn ui(f: &mut Frame, app: &App) {
                 );
             f.render_widget(net_chart, chart_chunks[2]);
         }
+
+        // Process count chart
+        if app.config.show_process_chart && !app.process_count_history.is_empty() {
+            let count_data = app.timed_series(&app.process_count_history);
+            let max_count = app
+                .process_count_history
+                .iter()
+                .cloned()
+                .fold(1.0_f64, |m, v| m.max(v));
+            let count_cursor = cursor_line(max_count * 1.2);
+            let mut datasets = vec![
+                Dataset::default()
+                    .name("Processes")
+                    .marker(marker)
+                    .graph_type(graph_type)
+                    .style(Style::default().fg(app.theme.accent))
+                    .data(&count_data),
+            ];
+            if !count_cursor.is_empty() {
+                datasets.push(
+                    Dataset::default()
+                        .graph_type(GraphType::Line)
+                        .style(Style::default().fg(app.theme.fg))
+                        .data(&count_cursor),
+                );
+            }
+            let count_title = match (cursor_age, cursor_value(&app.process_count_history)) {
+                (Some(age), Some(v)) => format!("Processes [-{:.0}s: {:.0}]", age, v),
+                _ => format!(
+                    "Processes ({:.0})",
+                    app.process_count_history.back().copied().unwrap_or(0.0)
+                ),
+            };
+            let count_chart = Chart::new(datasets)
+                .block(Block::default().title(count_title).borders(Borders::ALL))
+                .x_axis(Axis::default().title("Time (s)").bounds([0.0, span]))
+                .y_axis(
+                    Axis::default()
+                        .title("Count")
+                        .bounds([0.0, max_count * 1.2]),
+                );
+            f.render_widget(count_chart, chart_chunks[3]);
+        }
+    }
+
+    // Plugin metrics panel (only when a monitoring plugin has reported something)
+    if show_plugin_metrics {
+        let mut spans = Vec::new();
+        for (name, value) in &app.plugin_metrics {
+            spans.push(Span::styled(
+                format!("{}: ", name),
+                Style::default().fg(app.theme.accent),
+            ));
+            spans.push(Span::styled(
+                format!("{:.2}", value),
+                Style::default()
+                    .fg(app.theme.fg)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::raw("   "));
+        }
+        let metrics_widget = Paragraph::new(Line::from(spans))
+            .style(Style::default().fg(app.theme.fg).bg(app.theme.bg))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Plugin Metrics")
+                    .border_style(Style::default().fg(app.theme.accent)),
+            );
+        f.render_widget(metrics_widget, chunks[chunks.len() - 2]);
     }
 
     // Process list - only collect what fits on screen (configurable)
     let selected = app.selected.min(app.process_view.len().saturating_sub(1));
+    // The COMMAND column can never be wider than the terminal, so cap huge cmdlines up front
+    let max_cmd_chars = f.area().width as usize;
+    let matchers: Vec<TermMatcher> = std::iter::once(&app.search)
+        .chain(app.filters.iter())
+        .map(|t| t.trim())
+        .filter(|t| !t.is_empty())
+        .map(|t| TermMatcher::new_or_literal(t, app.match_mode()))
+        .collect();
+    let match_style = Style::default()
+        .fg(app.theme.warn)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    // (header, width); COMMAND takes whatever is left over, but at least its width
+    let ppid_width = if app.config.show_parent_name { 20 } else { 7 };
+    let mut columns: Vec<(&str, u16)> = vec![
+        ("", 1), // New-process gutter
+        ("NI", 4),
+        ("PRI", 4),
+        ("PID", 7),
+        ("PPID", ppid_width),
+        ("USER", 10),
+    ];
+    if app.config.show_cgroup {
+        columns.push(("CGROUP", 20));
+    }
+    columns.extend([
+        ("COMMAND", 24),
+        ("TIME", 9),
+        ("MEM", 10),
+        ("CPU%", 7),
+        ("VIRT", 10),
+        ("SHR", 10),
+        ("READ/s", 10),
+        ("WRITE/s", 10),
+        ("S", 3),
+    ]);
+    // Narrow terminals lose the least essential columns first, so PID and COMMAND stay
+    // readable instead of every column being squeezed to a sliver
+    const DROP_ORDER: &[&str] = &[
+        "WRITE/s", "READ/s", "SHR", "VIRT", "CGROUP", "PRI", "NI", "PPID", "TIME",
+    ];
+    let table_width = chunks[chunks.len() - 1]
+        .width
+        .saturating_sub(if app.zen { 0 } else { 2 });
+    // Each column plus its one-cell gap, and the ">> " highlight symbol
+    let needed = |hidden: &[&str]| -> u16 {
+        columns
+            .iter()
+            .filter(|(name, _)| !hidden.contains(name))
+            .map(|(_, width)| width + 1)
+            .sum::<u16>()
+            + 3
+    };
+    let mut hidden: Vec<&str> = Vec::new();
+    for name in DROP_ORDER {
+        if needed(&hidden) <= table_width {
+            break;
+        }
+        hidden.push(name);
+    }
+    let shown: Vec<bool> = columns
+        .iter()
+        .map(|(name, _)| !hidden.contains(name))
+        .collect();
+
     let process_items: Vec<Row> = app
         .process_view
         .iter()
@@ -1341,77 +5111,150 @@ fn ui(f: &mut Frame, app: &App) {
                 (p.time_total_secs / 60) % 60,
                 p.time_total_secs % 60
             );
-            let row = Row::new(vec![
+            let gutter = if app.new_pids.contains(&p.pid) {
+                Cell::from("+").style(Style::default().fg(theme::get_run_color(&app.theme)))
+            } else {
+                Cell::from(" ")
+            };
+            let mut cells = vec![
+                gutter,
                 Cell::from(p.nice.to_string()),
                 Cell::from(p.priority.to_string()),
                 Cell::from(p.pid.to_string()),
+                Cell::from(match p.ppid {
+                    Some(ppid) if app.config.show_parent_name => {
+                        match app.processes.iter().find(|q| q.pid == ppid) {
+                            Some(parent) => format!("{} {}", ppid, short_command(&parent.command)),
+                            None => ppid.to_string(),
+                        }
+                    }
+                    Some(ppid) => ppid.to_string(),
+                    None => "-".to_string(),
+                }),
                 Cell::from(p.user.as_str()),
-                Cell::from(p.command.as_str()),
+            ];
+            // Optional columns sit between USER and COMMAND
+            if app.config.show_cgroup {
+                cells.push(Cell::from(p.cgroup.as_str()));
+            }
+            let command = match app.tree_prefixes.get(idx) {
+                Some(prefix) => format!("{}{}", prefix, p.command),
+                None => p.command.clone(),
+            };
+            let command = truncate_chars(&command, max_cmd_chars).into_owned();
+            // Highlight what the search (or else the first matching filter) hit
+            let command_cell = match matchers.iter().find_map(|m| m.find(&command)) {
+                Some(ranges) => Cell::from(highlight_matches(&command, &ranges, match_style)),
+                None => Cell::from(command),
+            };
+            cells.extend([
+                command_cell,
                 Cell::from(fmt_time),
                 Cell::from(humansize::format_size(p.mem_bytes, humansize::DECIMAL)),
                 Cell::from(format!("{:.1}", p.cpu_usage)),
                 Cell::from(humansize::format_size(p.virt, humansize::DECIMAL)),
-                Cell::from(humansize::format_size(p.res, humansize::DECIMAL)),
                 Cell::from(humansize::format_size(p.shr, humansize::DECIMAL)),
+                Cell::from(humansize::format_size(
+                    p.disk_read_bytes_per_sec as u64,
+                    humansize::DECIMAL,
+                )),
+                Cell::from(humansize::format_size(
+                    p.disk_write_bytes_per_sec as u64,
+                    humansize::DECIMAL,
+                )),
                 Cell::from(p.state.to_string()),
             ]);
+            let cells = cells
+                .into_iter()
+                .zip(&shown)
+                .filter_map(|(cell, &show)| show.then_some(cell));
+            let row = Row::new(cells);
             if idx == selected {
                 row.style(Style::default().bg(app.theme.selection_bg))
+            } else if app.config.quiet_mode.as_deref() == Some("dim") && is_quiet(p, &app.config) {
+                row.style(
+                    Style::default()
+                        .fg(app.theme.bar_empty)
+                        .add_modifier(Modifier::DIM),
+                )
             } else {
                 row
             }
         })
         .collect();
 
-    let process_table = Table::new(
-        process_items,
-        [
-            Constraint::Length(4),  // NI
-            Constraint::Length(4),  // PRI
-            Constraint::Length(7),  // PID
-            Constraint::Length(10), // USER
-            Constraint::Min(24),    // COMMAND
-            Constraint::Length(9),  // TIME
-            Constraint::Length(10), // MEM
-            Constraint::Length(7),  // CPU%
-            Constraint::Length(10), // VIRT
-            Constraint::Length(10), // RES
-            Constraint::Length(10), // SHR
-            Constraint::Length(3),  // S
-        ],
-    )
-    .header(
-        Row::new(vec![
-            "NI", "PRI", "PID", "USER", "COMMAND", "TIME", "MEM", "CPU%", "VIRT", "RES", "SHR", "S",
-        ])
-        .style(Style::default().fg(app.theme.table_header)),
-    )
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title("Processes (F3 Search, F4 Filter, F6 Sort, F7/F8 Nice, F9 Kill)")
-            .border_style(Style::default().fg(app.theme.accent)),
-    )
-    .row_highlight_style(
-        Style::default()
-            .bg(app.theme.selection_bg)
-            .fg(app.theme.accent)
-            .add_modifier(Modifier::BOLD),
-    )
-    .highlight_symbol(">> ");
+    let header: Vec<&str> = columns
+        .iter()
+        .filter(|(name, _)| !hidden.contains(name))
+        .map(|(name, _)| *name)
+        .collect();
+    let widths: Vec<Constraint> = columns
+        .iter()
+        .filter(|(name, _)| !hidden.contains(name))
+        .map(|&(name, width)| {
+            if name == "COMMAND" {
+                Constraint::Min(width)
+            } else {
+                Constraint::Length(width)
+            }
+        })
+        .collect();
+
+    let process_table = Table::new(process_items, widths)
+        .header(Row::new(header).style(Style::default().fg(app.theme.table_header)))
+        .block(if app.zen {
+            Block::default()
+        } else {
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Processes (F3 Search, F4 Filter, F6 Sort, F7/F8 Nice, F9 Kill)")
+                .border_style(Style::default().fg(app.theme.accent))
+        })
+        .row_highlight_style(
+            Style::default()
+                .bg(app.theme.selection_bg)
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
 
     let mut table_state = TableState::default();
     table_state.select(Some(selected));
-    let proc_idx = if app.config.show_charts { 4 } else { 3 };
+    let proc_idx = chunks.len() - 1;
     f.render_stateful_widget(process_table, chunks[proc_idx], &mut table_state);
+    // Data rows start below the border (none in zen mode) and the one-line header
+    let table_inner = if app.zen {
+        chunks[proc_idx]
+    } else {
+        chunks[proc_idx].inner(Margin::new(1, 1))
+    };
+    let rows_area = Rect {
+        y: table_inner.y.saturating_add(1),
+        height: table_inner.height.saturating_sub(1),
+        ..table_inner
+    };
+    app.process_rows_area.set((rows_area, table_state.offset()));
 
     // Overlays
     match app.overlay {
         Overlay::Help => {
             let area = centered_rect(70, 60, f.area());
-            let help_text = obfstr!(
-                "🚀 LYVOXA STELLAR CONTROLS 🚀\n\nPROCESS MANAGEMENT:\nF1 Help      F6 Sort modes    F9 Kill process\nF2 Setup     F7 Nice decrease ↑↓ Navigate\nF3 Search    F8 Nice increase Enter/Esc dialogs\nF4 Filter    F10 Quit\nF5 Charts toggle\n\nADVANCED FEATURES:\nF11 Export snapshot (JSON)\nF12 AI System Insights\nTab Cycle themes (3 elite themes)\n\nELITE THEMES:\nDark → Stellar → Matrix (cycle with Tab)\n\nConfig: ~/.config/lyvoxa/config.toml\nPress ESC to close this help window"
-            ).to_string();
+            let rocket = app.icon("🚀", "*");
+            // Say up front what will fail, rather than leaving it to an EPERM after F9
+            let privilege_note = if app.unprivileged {
+                "Running unprivileged: F7 and killing other users' processes need root\n\n"
+            } else {
+                ""
+            };
+            let help_text = format!(
+                "{} LYVOXA STELLAR CONTROLS {}\n\n{}{}",
+                rocket,
+                rocket,
+                privilege_note,
+                obfstr!(
+                    "PROCESS MANAGEMENT:\nF1 Help      F6 Sort modes    F9 Kill process\nF2 Setup     F7 Nice decrease ↑↓ Navigate\nF3 Search    F8 Nice increase Enter/Esc dialogs\nF4 Filter    F10 Quit\nF5 Charts toggle Enter Process details\nl  Event log (process start/exit)\ni  Quiet processes: dim/hide/show\nz  Zen mode (hide header and borders)\nc  Chart cursor (←/→ inspect history values)\np  Reload plugins (re-reads plugins.toml)\nR  Restart process (SIGTERM, then offer re-run)\nM  Manual refresh mode (r refreshes on demand)\nb  System info (kernel, boot parameters)\nn  Network rates per interface\nd  Disk usage per mounted filesystem\nP  Refresh profile (performance/balanced/battery)\nt  Tree view (Space folds the selected subtree)\nShift+F9 Send a signal: pick one, or type a name/number (USR1, 10)\nK  Quick kill: SIGKILL, no dialog (needs quick_kill = true)\nMouse: click selects a process, wheel scrolls\no  Reverse sort direction (also Shift+F6)\nN  Renice everything matching the search/filter\nTab in F3/F4: substring, regex or fuzzy matching\n\nADVANCED FEATURES:\nF11 Export snapshot (JSON)\nF12 AI System Insights\nTab Cycle themes (3 elite themes)\n\nELITE THEMES:\nDark → Stellar → Matrix (cycle with Tab)\n\nConfig: ~/.config/lyvoxa/config.toml\nPress ESC to close this help window"
+                )
+            );
             f.render_widget(Clear, area);
             let p = Paragraph::new(help_text)
                 .style(Style::default().fg(app.theme.fg).bg(app.theme.bg))
@@ -1423,24 +5266,105 @@ fn ui(f: &mut Frame, app: &App) {
                 );
             f.render_widget(p, area);
         }
+        Overlay::ExportMenu => {
+            let area = centered_rect(50, 40, f.area());
+            f.render_widget(Clear, area);
+            let mut lines = vec!["Choose a format (↑/↓, Enter export, Esc cancel)".to_string()];
+            lines.push(String::new());
+            for (i, target) in app.export_targets.iter().enumerate() {
+                let marker = if i == app.export_selected { ">" } else { " " };
+                let label = match target {
+                    ExportTarget::Json if app.config.export_compress => {
+                        "JSON (gzip, built-in)".to_string()
+                    }
+                    ExportTarget::Json => "JSON (built-in)".to_string(),
+                    ExportTarget::Plugin { name, format } => {
+                        format!("{} (plugin: {})", format.to_uppercase(), name)
+                    }
+                };
+                lines.push(format!("{} {}", marker, label));
+            }
+            let p = Paragraph::new(lines.join("\n"))
+                .style(Style::default().fg(app.theme.fg).bg(app.theme.bg))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Export Format")
+                        .style(Style::default().fg(app.theme.accent)),
+                );
+            f.render_widget(p, area);
+        }
         Overlay::Setup => {
             let area = centered_rect(80, 70, f.area());
             f.render_widget(Clear, area);
             let mut lines = Vec::new();
             lines.push(format!(
-                "Select config (↑/↓ navigate, Enter apply, r refresh, Esc close)\nCurrent: {} [{}]\n",
+                "Select config (↑/↓ navigate, Enter apply, e edit current, D reset defaults, r refresh, Esc close)\nCurrent: {} [{}]\n",
                 app.config_path.display(), config_source_label(app.config_source)
             ));
+            if !app.env_overrides.is_empty() {
+                lines.push("Env overrides (session only):".to_string());
+                for o in &app.env_overrides {
+                    lines.push(format!(
+                        "  {} = {:?}  -> {} = {}",
+                        o.var,
+                        o.value,
+                        o.field,
+                        match o.field {
+                            "ui_rate_ms" => app.config.ui_rate_ms.to_string(),
+                            "data_rate_ms" => app.config.data_rate_ms.to_string(),
+                            "max_rows" => app.config.max_rows.to_string(),
+                            _ => app.config.show_charts.to_string(),
+                        }
+                    ));
+                }
+                lines.push(String::new());
+            }
+            // Only the highest-priority file is read; the rest are silently ignored
+            let shadowed = app
+                .setup_sources
+                .iter()
+                .zip(&app.setup_states)
+                .filter(|((p, _), state)| {
+                    p.as_path() != app.config_path.as_path()
+                        && matches!(
+                            state,
+                            CandidateState::SameAsActive | CandidateState::DiffersFromActive
+                        )
+                })
+                .count();
+            if shadowed > 0 {
+                lines.push(format!(
+                    "{} {} other config file(s) exist but are ignored; edits to them have no effect",
+                    app.icon("⚠️ ", "[!]"),
+                    shadowed
+                ));
+                lines.push(String::new());
+            }
             if app.setup_sources.is_empty() {
                 lines.push("(no candidates found)".to_string());
             } else {
                 for (i, (p, src)) in app.setup_sources.iter().enumerate() {
                     let marker = if i == app.setup_selected { ">" } else { " " };
+                    let note = if p.as_path() == app.config_path.as_path() {
+                        "  <- active"
+                    } else {
+                        match app.setup_states.get(i) {
+                            Some(CandidateState::Missing) => "  (missing)",
+                            Some(CandidateState::Invalid) => "  (invalid)",
+                            Some(CandidateState::SameAsActive) => "  (ignored, same settings)",
+                            Some(CandidateState::DiffersFromActive) => {
+                                "  (ignored, DIFFERENT settings)"
+                            }
+                            None => "",
+                        }
+                    };
                     lines.push(format!(
-                        "{} [{}] {}",
+                        "{} [{}] {}{}",
                         marker,
                         config_source_label(*src),
-                        p.display()
+                        p.display(),
+                        note
                     ));
                 }
             }
@@ -1454,11 +5378,64 @@ fn ui(f: &mut Frame, app: &App) {
                 );
             f.render_widget(p, area);
         }
+        Overlay::ConfigEditor => {
+            // Narrower than Setup so edits can be previewed on the screen behind it
+            let area = centered_rect(60, 80, f.area());
+            f.render_widget(Clear, area);
+            let mut lines = vec![
+                if app.editor_editing {
+                    "Enter apply, Esc cancel edit (empty = default)".to_string()
+                } else {
+                    "↑/↓ select, Enter edit, Space toggle, d default, s save, Esc discard"
+                        .to_string()
+                },
+                format!("File: {}", app.config_path.display()),
+                String::new(),
+            ];
+            // Keep the selected row visible when the list is taller than the box
+            let visible = (area.height as usize)
+                .saturating_sub(2 + lines.len())
+                .max(1);
+            let start = (app.editor_selected + 1).saturating_sub(visible);
+            for (i, key) in CONFIG_EDITOR_FIELDS
+                .iter()
+                .enumerate()
+                .skip(start)
+                .take(visible)
+            {
+                let selected = i == app.editor_selected;
+                let value = if selected && app.editor_editing {
+                    format!("{}_", app.input_buffer)
+                } else {
+                    config_field_value(&app.config, key).unwrap_or_else(|| "(unset)".to_string())
+                };
+                let changed = app.editor_original.as_ref().is_some_and(|o| {
+                    config_field_value(o, key) != config_field_value(&app.config, key)
+                });
+                lines.push(format!(
+                    "{}{} {:<22} {}",
+                    if selected { ">" } else { " " },
+                    if changed { "*" } else { " " },
+                    key,
+                    value
+                ));
+            }
+            let p = Paragraph::new(lines.join("\n"))
+                .style(Style::default().fg(app.theme.fg).bg(app.theme.bg))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Setup - Edit Config")
+                        .style(Style::default().fg(app.theme.accent)),
+                );
+            f.render_widget(p, area);
+        }
         Overlay::Search => {
             let area = centered_rect(60, 30, f.area());
             let text = format!(
-                "Search query: {}\nPress Enter to apply or Esc to cancel",
-                app.input_buffer
+                "Search query: {}\nMatch: {} (Tab to change)\nPress Enter to apply or Esc to cancel",
+                app.input_buffer,
+                app.match_mode().as_str()
             );
             f.render_widget(Clear, area);
             let p = Paragraph::new(text)
@@ -1471,11 +5448,12 @@ fn ui(f: &mut Frame, app: &App) {
                 );
             f.render_widget(p, area);
         }
-        Overlay::Filter => {
+        Overlay::BatchNice => {
             let area = centered_rect(60, 30, f.area());
             let text = format!(
-                "Filter term: {}\nPress Enter to apply or Esc to cancel",
-                app.input_buffer
+                "New nice value (-20..19): {}\nApplies to every process matching: {}\nPress Enter to review or Esc to cancel",
+                app.input_buffer,
+                app.batch_terms().join(" AND ")
             );
             f.render_widget(Clear, area);
             let p = Paragraph::new(text)
@@ -1483,7 +5461,35 @@ fn ui(f: &mut Frame, app: &App) {
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .title("Filter")
+                        .title("Batch Renice")
+                        .style(Style::default().fg(app.theme.accent)),
+                );
+            f.render_widget(p, area);
+        }
+        Overlay::Filter => {
+            let area = centered_rect(60, 30, f.area());
+            let mut lines = vec![
+                format!("Add filter: {}", app.input_buffer),
+                format!("Match: {} (Tab to change)", app.match_mode().as_str()),
+                "Enter add | Esc close | Backspace on empty removes last".to_string(),
+                "Up/Down select | Del remove selected".to_string(),
+                String::new(),
+            ];
+            if app.filters.is_empty() {
+                lines.push("(no active filters)".to_string());
+            }
+            for (i, term) in app.filters.iter().enumerate() {
+                let marker = if i == app.filter_selected { ">" } else { " " };
+                lines.push(format!("{} [{}]", marker, term));
+            }
+            let text = lines.join("\n");
+            f.render_widget(Clear, area);
+            let p = Paragraph::new(text)
+                .style(Style::default().fg(app.theme.fg).bg(app.theme.bg))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Filter (AND)")
                         .style(Style::default().fg(app.theme.accent)),
                 );
             f.render_widget(p, area);
@@ -1498,7 +5504,10 @@ fn ui(f: &mut Frame, app: &App) {
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .title("🤖 AI System Insights (Press Esc to close)")
+                        .title(format!(
+                            "{} AI System Insights (m: save Markdown report, Esc: close)",
+                            app.icon("🤖", "[AI]")
+                        ))
                         .style(Style::default().fg(app.theme.accent)),
                 )
                 .wrap(ratatui::widgets::Wrap { trim: true });
@@ -1506,7 +5515,34 @@ fn ui(f: &mut Frame, app: &App) {
         }
         Overlay::Export => {
             let area = centered_rect(60, 30, f.area());
-            let export_text = "📤 Exporting system snapshot...\n\nData will be saved as JSON with:\n• System metrics\n• Process information\n• Theme configuration";
+            let export_text = match &app.export_state {
+                ExportState::Running { started } => {
+                    const SPINNER: [&str; 4] = ["|", "/", "-", "\\"];
+                    let frame = (started.elapsed().as_millis() / 100) as usize % SPINNER.len();
+                    format!(
+                        "{} Exporting system snapshot... {}",
+                        app.icon("📤", "[export]"),
+                        SPINNER[frame]
+                    )
+                }
+                ExportState::Done {
+                    path,
+                    bytes,
+                    elapsed,
+                } => format!(
+                    "{} Snapshot saved to:\n\n{}\n\nSize: {}   Time: {:.2}s\n\nPress Esc or Enter to close",
+                    app.icon("📄", "[export]"),
+                    path,
+                    humansize::format_size(*bytes, humansize::DECIMAL),
+                    elapsed.as_secs_f64()
+                ),
+                ExportState::Failed(e) => format!(
+                    "{} Export failed:\n\n{}\n\nPress Esc or Enter to close",
+                    app.icon("❌", "[error]"),
+                    e
+                ),
+                ExportState::Idle => format!("{} No export yet", app.icon("📤", "[export]")),
+            };
             f.render_widget(Clear, area);
             let p = Paragraph::new(export_text)
                 .style(Style::default().fg(app.theme.fg).bg(app.theme.bg))
@@ -1518,6 +5554,373 @@ fn ui(f: &mut Frame, app: &App) {
                 );
             f.render_widget(p, area);
         }
+        Overlay::Detail => {
+            let area = centered_rect(80, 70, f.area());
+            f.render_widget(Clear, area);
+            let mut lines = Vec::new();
+            match app
+                .detail_pid
+                .and_then(|pid| app.processes.iter().find(|p| p.pid == pid))
+            {
+                Some(p) => {
+                    lines.push(format!("PID: {}   PPID: {}", p.pid, p.ppid.unwrap_or(0)));
+                    lines.push(format!("User: {}   State: {}", p.user, p.state));
+                    lines.push(match &app.detail_sched {
+                        Some(Ok(policy)) if policy.is_realtime() => format!(
+                            "Scheduling: {} (real-time: preempts normal tasks, can monopolize a core)",
+                            policy.label()
+                        ),
+                        Some(Ok(policy)) => format!("Scheduling: {}", policy.label()),
+                        Some(Err(e)) => format!("Scheduling: unavailable ({})", e),
+                        None => "Scheduling: unavailable".to_string(),
+                    });
+                    // In D state this usually names the lock or I/O path the process is stuck on
+                    lines.push(match &app.detail_wchan {
+                        Some(wchan) if p.state == 'D' => {
+                            format!("Waiting in: {} (uninterruptible)", wchan)
+                        }
+                        Some(wchan) => format!("Waiting in: {}", wchan),
+                        None => "Waiting in: - (running or not visible)".to_string(),
+                    });
+                    lines.push(String::new());
+                    // Mostly-kernel time hints at syscall/IO churn rather than computation
+                    let cpu_total = p.user_time_secs + p.sys_time_secs;
+                    let share = |s: u64| {
+                        if cpu_total == 0 {
+                            0.0
+                        } else {
+                            s as f64 / cpu_total as f64 * 100.0
+                        }
+                    };
+                    lines.push("CPU time:".to_string());
+                    lines.push(format!(
+                        "  User: {}s ({:.0}%)   System: {}s ({:.0}%)",
+                        p.user_time_secs,
+                        share(p.user_time_secs),
+                        p.sys_time_secs,
+                        share(p.sys_time_secs)
+                    ));
+                    lines.push(String::new());
+                    // Pss splits shared pages between their users, so it is the honest cost
+                    lines.push("Memory map (smaps_rollup):".to_string());
+                    match &app.detail_mem {
+                        Some(Ok(m)) => {
+                            let fmt = |b: u64| humansize::format_size(b, humansize::DECIMAL);
+                            lines.push(format!(
+                                "  Pss: {}   Rss: {}   Swap: {}",
+                                fmt(m.pss),
+                                fmt(m.rss),
+                                fmt(m.swap)
+                            ));
+                            lines.push(format!(
+                                "  Private: {}   Shared: {}",
+                                fmt(m.private),
+                                fmt(m.shared)
+                            ));
+                        }
+                        Some(Err(e)) => lines.push(format!("  unavailable: {}", e)),
+                        None => lines.push("  unavailable".to_string()),
+                    }
+                    lines.push(String::new());
+                    lines.push(format!("Arguments ({}):", p.args.len()));
+                    if p.args.is_empty() {
+                        lines.push(format!("  (kernel thread) {}", p.command));
+                    }
+                    // One argv entry per line, quoted so embedded spaces stay unambiguous
+                    for (i, arg) in p.args.iter().enumerate() {
+                        lines.push(format!("  argv[{}] {:?}", i, arg));
+                    }
+                }
+                None => lines.push("Process has exited".to_string()),
+            }
+            let p = Paragraph::new(lines.join("\n"))
+                .style(Style::default().fg(app.theme.fg).bg(app.theme.bg))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Process Details (Esc to close)")
+                        .style(Style::default().fg(app.theme.accent)),
+                )
+                .wrap(ratatui::widgets::Wrap { trim: false });
+            f.render_widget(p, area);
+        }
+        Overlay::Events => {
+            let area = centered_rect(80, 70, f.area());
+            f.render_widget(Clear, area);
+            let text = if app.event_log.is_empty() {
+                "(no events yet)".to_string()
+            } else {
+                // Newest first
+                app.event_log
+                    .iter()
+                    .rev()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            let p = Paragraph::new(text)
+                .style(Style::default().fg(app.theme.fg).bg(app.theme.bg))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Event Log (Esc to close)")
+                        .style(Style::default().fg(app.theme.accent)),
+                );
+            f.render_widget(p, area);
+        }
+        Overlay::Signal => {
+            let area = centered_rect(60, 40, f.area());
+            f.render_widget(Clear, area);
+            let target = app
+                .signal_pid
+                .and_then(|pid| app.processes.iter().find(|p| p.pid == pid))
+                .map_or_else(
+                    || "process has exited".to_string(),
+                    |p| {
+                        format!(
+                            "PID {} ({}) {}",
+                            p.pid,
+                            p.user,
+                            truncate_chars(&p.command, 40)
+                        )
+                    },
+                );
+            let mut lines = vec![
+                format!("Send to {}", target),
+                "↑/↓ choose, or type a name/number (USR1, 10); Enter send, Esc cancel".to_string(),
+                format!("Signal: {}_", app.input_buffer),
+                String::new(),
+            ];
+            for (i, (signal, what)) in SIGNAL_CHOICES.iter().enumerate() {
+                let marker = if i == app.signal_selected { ">" } else { " " };
+                lines.push(format!("{} {:<8} {}", marker, signal.as_str(), what));
+            }
+            let p = Paragraph::new(lines.join("\n"))
+                .style(Style::default().fg(app.theme.fg).bg(app.theme.bg))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Send Signal")
+                        .style(Style::default().fg(app.theme.warn)),
+                );
+            f.render_widget(p, area);
+        }
+        Overlay::Network => {
+            let area = centered_rect(70, 60, f.area());
+            f.render_widget(Clear, area);
+            let mut lines = vec![format!(
+                "{:<16} {:>12} {:>12} {:>12} {:>12}",
+                "INTERFACE", "RX/s", "TX/s", "RX total", "TX total"
+            )];
+            if app.iface_rates.is_empty() {
+                lines.push("(no interfaces)".to_string());
+            }
+            for (iface, rx, tx) in &app.iface_rates {
+                let (rx_total, tx_total) = app.monitor.get_iface_totals(iface).unwrap_or((0, 0));
+                lines.push(format!(
+                    "{:<16} {:>12} {:>12} {:>12} {:>12}",
+                    truncate_chars(iface, 16),
+                    humansize::format_size(*rx as u64, humansize::DECIMAL),
+                    humansize::format_size(*tx as u64, humansize::DECIMAL),
+                    humansize::format_size(rx_total, humansize::DECIMAL),
+                    humansize::format_size(tx_total, humansize::DECIMAL)
+                ));
+            }
+            if !app.config.network_skip_interfaces.is_empty() {
+                lines.push(String::new());
+                lines.push(format!(
+                    "Skipped: {} (network_skip_interfaces)",
+                    app.config.network_skip_interfaces.join(", ")
+                ));
+            }
+            let p = Paragraph::new(lines.join("\n"))
+                .style(Style::default().fg(app.theme.fg).bg(app.theme.bg))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Network Interfaces (Esc to close)")
+                        .style(Style::default().fg(app.theme.accent)),
+                );
+            f.render_widget(p, area);
+        }
+        Overlay::Disks => {
+            let area = centered_rect(80, 60, f.area());
+            f.render_widget(Clear, area);
+            let size = |b: u64| humansize::format_size(b, humansize::DECIMAL);
+            let mut lines = vec![Line::from(format!(
+                "{:<20} {:<8} {:>10} {:>10} {:>10}  USE",
+                "MOUNT", "FS", "SIZE", "USED", "FREE"
+            ))];
+            let disks: Vec<monitor::DiskInfo> = app
+                .monitor
+                .get_disks()
+                .into_iter()
+                .filter(|d| app.config.disk_show_pseudo || !d.is_pseudo())
+                .collect();
+            if disks.is_empty() {
+                lines.push(Line::from("(no filesystems)"));
+            }
+            for disk in &disks {
+                let used = disk.used_percent();
+                let mut spans = vec![Span::raw(format!(
+                    "{:<20} {:<8} {:>10} {:>10} {:>10}  [",
+                    truncate_chars(&disk.mount_point, 20),
+                    truncate_chars(&disk.fs_type, 8),
+                    size(disk.total_bytes),
+                    size(disk.used_bytes()),
+                    size(disk.available_bytes)
+                ))];
+                spans.extend(make_colored_bar(used as f32, 20, &app.theme));
+                spans.push(Span::styled(
+                    format!("] {:.0}%", used),
+                    Style::default().fg(app.severity_color(used, app.theme.fg)),
+                ));
+                lines.push(Line::from(spans));
+            }
+            if !app.config.disk_show_pseudo {
+                lines.push(Line::from(""));
+                lines.push(Line::from(
+                    "tmpfs and other memory-backed mounts hidden (disk_show_pseudo)",
+                ));
+            }
+            let p = Paragraph::new(lines)
+                .style(Style::default().fg(app.theme.fg).bg(app.theme.bg))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Disks (Esc to close)")
+                        .style(Style::default().fg(app.theme.accent)),
+                );
+            f.render_widget(p, area);
+        }
+        Overlay::SystemInfo => {
+            let area = centered_rect(80, 70, f.area());
+            f.render_widget(Clear, area);
+            let mut lines = Vec::new();
+            if let Some((info, boot)) = &app.system_info {
+                lines.push(format!("Host: {}", info.hostname));
+                lines.push(format!("OS: {}", info.os_version));
+                lines.push(format!("Kernel: {}", info.kernel_version));
+                lines.push(format!(
+                    "Uptime: {}d {:02}:{:02}",
+                    info.uptime / 86400,
+                    (info.uptime / 3600) % 24,
+                    (info.uptime / 60) % 60
+                ));
+                lines.push(String::new());
+                lines.push("Kernel build (/proc/version):".to_string());
+                lines.push(format!("  {}", boot.version));
+                lines.push(String::new());
+                // One parameter per line so mitigations=, hugepages= etc. are easy to spot
+                lines.push("Boot parameters (/proc/cmdline):".to_string());
+                if boot.cmdline.is_empty() {
+                    lines.push("  unavailable".to_string());
+                }
+                for param in boot.cmdline.split_whitespace() {
+                    lines.push(format!("  {}", param));
+                }
+            }
+            let p = Paragraph::new(lines.join("\n"))
+                .style(Style::default().fg(app.theme.fg).bg(app.theme.bg))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("System Info (Esc to close)")
+                        .style(Style::default().fg(app.theme.accent)),
+                )
+                .wrap(ratatui::widgets::Wrap { trim: false });
+            f.render_widget(p, area);
+        }
+        Overlay::Confirm => {
+            // File previews need more room than a one-line question
+            let area = if matches!(
+                app.pending_action,
+                Some(PendingAction::CreateConfig { .. } | PendingAction::BatchRenice { .. })
+            ) {
+                centered_rect(70, 80, f.area())
+            } else {
+                centered_rect(60, 30, f.area())
+            };
+            f.render_widget(Clear, area);
+            let text = match &app.pending_action {
+                Some(PendingAction::Renice { pid, from, to }) => format!(
+                    "{} Renice PID {} from {} to {}?\n\nNegative nice values take CPU priority from the rest of\nthe system and can starve it if pushed too far.\n\nPress y/Enter to confirm, any other key to cancel",
+                    app.icon("⚠️ ", "[!]"),
+                    pid,
+                    from,
+                    to
+                ),
+                Some(PendingAction::Restart { pid, args, cwd }) => format!(
+                    "{} Restart PID {}?\n\nSends SIGTERM; if nothing respawns it, you will be asked to re-run:\n\n  {}\n  (cwd: {})\n\nPress y to confirm, any other key to cancel",
+                    app.icon("⚠️ ", "[!]"),
+                    pid,
+                    args.join(" "),
+                    cwd.as_deref()
+                        .map_or("unknown".to_string(), |c| c.display().to_string())
+                ),
+                Some(PendingAction::Reexec { args, cwd }) => format!(
+                    "{} The process did not come back. Re-run it as the current user?\n\n  {}\n  (cwd: {})\n\nOutput is discarded; the new process is detached from Lyvoxa.\n\nPress y/Enter to run, any other key to cancel",
+                    app.icon("⚠️ ", "[!]"),
+                    args.join(" "),
+                    cwd.as_deref()
+                        .map_or("unknown".to_string(), |c| c.display().to_string())
+                ),
+                Some(PendingAction::ResetConfig { path }) => format!(
+                    "{} Reset config to defaults?\n\nEvery setting returns to its built-in default, applied now\nand written to:\n\n  {}\n\nPress y/Enter to confirm, any other key to cancel",
+                    app.icon("⚠️ ", "[!]"),
+                    path.display()
+                ),
+                Some(PendingAction::Kill { pid, user, command }) => format!(
+                    "{} Send SIGTERM to this process?\n\n  PID:     {}\n  User:    {}\n  Command: {}\n\nPress y to kill, Esc/n to cancel",
+                    app.icon("⚠️ ", "[!]"),
+                    pid,
+                    user,
+                    truncate_chars(command, 200)
+                ),
+                Some(PendingAction::BatchRenice { nice, targets }) => {
+                    let mut lines: Vec<String> = targets
+                        .iter()
+                        .take(10)
+                        .map(|(pid, command)| {
+                            format!("  {:>7} {}", pid, truncate_chars(command, 60))
+                        })
+                        .collect();
+                    if targets.len() > 10 {
+                        lines.push(format!("  ... and {} more", targets.len() - 10));
+                    }
+                    format!(
+                        "{} Renice {} matching processes to {}?\n\n{}\n\nPress y to apply, Esc/n to cancel",
+                        app.icon("⚠️ ", "[!]"),
+                        targets.len(),
+                        nice,
+                        lines.join("\n")
+                    )
+                }
+                Some(PendingAction::CreateConfig { path, source }) => {
+                    let content = toml::to_string_pretty(&app.config).unwrap_or_default();
+                    let preview: Vec<String> =
+                        content.lines().map(|l| format!("  + {}", l)).collect();
+                    format!(
+                        "{} Create new config file [{}]?\n\n  {}\n\nIt does not exist yet and would be written with:\n\n{}\n\nPress y/Enter to create, any other key to cancel",
+                        app.icon("⚠️ ", "[!]"),
+                        config_source_label(*source),
+                        path.display(),
+                        preview.join("\n")
+                    )
+                }
+                None => String::new(),
+            };
+            let p = Paragraph::new(text)
+                .style(Style::default().fg(app.theme.fg).bg(app.theme.bg))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Confirm")
+                        .style(Style::default().fg(app.theme.warn)),
+                )
+                .wrap(ratatui::widgets::Wrap { trim: false });
+            f.render_widget(p, area);
+        }
         Overlay::None => {}
     }
 }
@@ -1549,6 +5952,26 @@ fn make_colored_bar(percent: f32, width: usize, theme: &Theme) -> Vec<Span<'stat
     spans
 }
 
+// Program name for compact display: basename of the first argv word
+fn short_command(cmd: &str) -> &str {
+    let first = cmd.split_whitespace().next().unwrap_or(cmd);
+    first.rsplit('/').next().unwrap_or(first)
+}
+
+// Cut a string to at most `max` characters, marking the cut with an ellipsis
+fn truncate_chars(s: &str, max: usize) -> Cow<'_, str> {
+    let mut chars = s.char_indices();
+    match (chars.nth(max.saturating_sub(1)), chars.next()) {
+        (Some((idx, _)), Some(_)) if max > 0 => {
+            let mut out = String::with_capacity(idx + 3);
+            out.push_str(&s[..idx]);
+            out.push('…');
+            Cow::Owned(out)
+        }
+        _ => Cow::Borrowed(s),
+    }
+}
+
 fn centered_rect(
     percent_x: u16,
     percent_y: u16,
@@ -1572,3 +5995,255 @@ fn centered_rect(
         .split(popup_layout[1]);
     vertical[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_app(show_charts: bool) -> App {
+        detached_app(AppConfig {
+            show_charts,
+            ..AppConfig::default()
+        })
+    }
+
+    #[test]
+    fn render_fits_any_terminal_size() {
+        for show_charts in [true, false] {
+            let app = render_app(show_charts);
+            for (w, h) in [(200, 60), (120, 40), (80, 24), (40, 12), (20, 6), (1, 1)] {
+                let text = render_to_text(&app, w, h)
+                    .unwrap_or_else(|e| panic!("{}x{} charts={}: {}", w, h, show_charts, e));
+                if w >= 80 && h >= 24 {
+                    for landmark in ["Lyvoxa", "Mem[", "Swp[", "PID", "COMMAND"] {
+                        assert!(
+                            text.contains(landmark),
+                            "{}x{} charts={}: missing {:?}",
+                            w,
+                            h,
+                            show_charts,
+                            landmark
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn render_every_overlay() {
+        let mut app = render_app(true);
+        app.detail_pid = app.processes.first().map(|p| p.pid);
+        app.signal_pid = app.detail_pid;
+        let overlays = [
+            (Overlay::Help, "Help"),
+            (Overlay::Setup, "Setup - Config Sources"),
+            (Overlay::ConfigEditor, "Setup - Edit Config"),
+            (Overlay::Search, "Search"),
+            (Overlay::Filter, "Filter (AND)"),
+            (Overlay::BatchNice, "Batch Renice"),
+            (Overlay::Insights, "System Insights"),
+            (Overlay::Export, "Export Snapshot"),
+            (Overlay::ExportMenu, "Export Format"),
+            (Overlay::Detail, "Process Details"),
+            (Overlay::Events, "Event Log"),
+            (Overlay::Signal, "Send Signal"),
+            (Overlay::Network, "Network Interfaces"),
+            (Overlay::Disks, "Disks"),
+            (Overlay::SystemInfo, "System Info"),
+            (Overlay::Confirm, "Confirm"),
+        ];
+        for (overlay, title) in overlays {
+            app.overlay = overlay;
+            for (w, h) in [(160, 50), (40, 12)] {
+                let text = render_to_text(&app, w, h).unwrap();
+                if w == 160 {
+                    assert!(text.contains(title), "{}x{}: missing {:?}", w, h, title);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn minimal_config_keeps_defaults() {
+        let cfg: AppConfig = toml::from_str("theme = \"matrix\"\n").unwrap();
+        assert_eq!(cfg.theme.as_deref(), Some("matrix"));
+        assert_eq!(cfg.ui_rate_ms, default_ui_rate_ms());
+        assert_eq!(cfg.data_rate_ms, default_data_rate_ms());
+        assert_eq!(cfg.max_rows, default_max_rows());
+        assert!(cfg.show_charts);
+        assert_eq!(cfg.kill_wait_ms, default_kill_wait_ms());
+        assert!(
+            cfg.header_fields
+                .iter()
+                .all(|f| HEADER_FIELDS.contains(&f.as_str()))
+        );
+    }
+
+    #[test]
+    fn empty_config_parses() {
+        let cfg: AppConfig = toml::from_str("").unwrap();
+        assert_eq!(cfg.ui_rate_ms, AppConfig::default().ui_rate_ms);
+        assert!(cfg.sort.is_none());
+    }
+
+    #[test]
+    fn unknown_keys_are_reported() {
+        let content = "ui_rate_ms = 250\nui_rate = 100\nsort = \"mem\"\n";
+        let (cfg, _, unknown) = parse_config(content).unwrap();
+        assert_eq!(cfg.ui_rate_ms, 250);
+        assert_eq!(unknown, Some(vec!["ui_rate".to_string()]));
+        let (_, _, unknown) = parse_config("sort = \"mem\"\n").unwrap();
+        assert_eq!(unknown, None);
+    }
+
+    #[test]
+    fn anonymize_strips_users_and_arguments() {
+        let proc_ = |user: &str, args: &[&str]| monitor::ProcessInfo {
+            user: user.to_string(),
+            command: args.join(" "),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            ..Default::default()
+        };
+        let mut procs = vec![
+            proc_("alice", &["/usr/bin/curl", "-H", "Authorization: secret"]),
+            proc_("bob", &["/home/bob/bin/tool"]),
+            proc_("alice", &[]),
+        ];
+        procs[2].command = "kworker/0:1".to_string();
+        anonymize_processes(&mut procs);
+        assert_eq!(procs[0].user, "user1");
+        assert_eq!(procs[0].command, "curl");
+        assert_eq!(procs[0].args, vec!["curl".to_string()]);
+        assert_eq!(procs[1].user, "user2");
+        assert_eq!(procs[1].command, "tool");
+        assert_eq!(procs[2].user, "user1");
+        assert_eq!(procs[2].command, "kworker/0:1");
+    }
+
+    #[test]
+    fn memory_pressure_ignores_plain_utilization() {
+        assert_eq!(
+            memory_pressure_level(0.0, 50.0, None),
+            MemoryPressureLevel::None
+        );
+        assert_eq!(
+            memory_pressure_level(150.0, 0.0, None),
+            MemoryPressureLevel::Moderate
+        );
+        assert_eq!(
+            memory_pressure_level(600.0, 600.0, None),
+            MemoryPressureLevel::Severe
+        );
+        let stalled = monitor::MemoryPressure {
+            some_avg10: 25.0,
+            full_avg10: 1.0,
+        };
+        assert_eq!(
+            memory_pressure_level(0.0, 0.0, Some(stalled)),
+            MemoryPressureLevel::Severe
+        );
+    }
+
+    #[test]
+    fn signals_parse_by_name_or_number() {
+        assert_eq!(parse_signal("10"), Ok(Signal::SIGUSR1));
+        assert_eq!(parse_signal("SIGUSR2"), Ok(Signal::SIGUSR2));
+        assert_eq!(parse_signal("abrt"), Ok(Signal::SIGABRT));
+        assert!(parse_signal("999").is_err());
+        assert!(parse_signal("SIGNOPE").is_err());
+    }
+
+    #[test]
+    fn term_matcher_modes() {
+        let fuzzy = TermMatcher::new("ffx", MatchMode::Fuzzy).unwrap();
+        assert_eq!(
+            fuzzy.find("/usr/lib/FireFox"),
+            Some(vec![(9, 10), (13, 14), (15, 16)])
+        );
+        assert!(!fuzzy.is_match("xff"));
+        let re = TermMatcher::new("^/usr/bin/(bash|zsh)$", MatchMode::Regex).unwrap();
+        assert!(re.is_match("/usr/bin/zsh"));
+        assert!(!re.is_match("/usr/bin/fish"));
+        assert!(TermMatcher::new("(unclosed", MatchMode::Regex).is_err());
+        let sub = TermMatcher::new("Fox", MatchMode::Substring).unwrap();
+        assert_eq!(sub.find("firefox"), Some(vec![(4, 7)]));
+    }
+
+    #[test]
+    fn tree_order_groups_children() {
+        let proc_ = |pid: u32, ppid: u32| monitor::ProcessInfo {
+            pid,
+            ppid: Some(ppid),
+            ..Default::default()
+        };
+        // 10 -> {11, 12}, 11 -> 13, and 20 whose parent 99 isn't listed
+        let procs = vec![
+            proc_(13, 11),
+            proc_(20, 99),
+            proc_(12, 10),
+            proc_(10, 1),
+            proc_(11, 10),
+        ];
+        let view: Vec<usize> = (0..procs.len()).collect();
+        let order = |collapsed: &HashSet<u32>| -> Vec<(u32, String)> {
+            tree_order(&procs, &view, collapsed, true)
+                .into_iter()
+                .map(|(i, prefix)| (procs[i].pid, prefix))
+                .collect()
+        };
+        assert_eq!(
+            order(&HashSet::new()),
+            vec![
+                (20, String::new()),
+                (10, String::new()),
+                (12, "|- ".to_string()),
+                (11, "`- ".to_string()),
+                (13, "   `- ".to_string()),
+            ]
+        );
+        let folded = order(&HashSet::from([11]));
+        assert_eq!(folded.len(), 4);
+        assert_eq!(folded[3], (11, "`- [+] ".to_string()));
+    }
+
+    #[test]
+    fn refresh_profile_overrides_rates() {
+        let (cfg, _, _) =
+            parse_config("refresh_profile = \"battery\"\nui_rate_ms = 100\n").unwrap();
+        assert_eq!(cfg.ui_rate_ms, 2000);
+        assert_eq!(cfg.data_rate_ms, 15000);
+        assert_eq!(cfg.history_len, 20);
+        let (cfg, _, _) = parse_config("refresh_profile = \"turbo\"\nui_rate_ms = 100\n").unwrap();
+        assert_eq!(cfg.ui_rate_ms, 100);
+    }
+
+    #[test]
+    fn editor_sets_and_resets_fields() {
+        let cfg = AppConfig::default();
+        let cfg = set_config_field(&cfg, "max_rows", "40").unwrap();
+        assert_eq!(cfg.max_rows, 40);
+        let cfg = set_config_field(&cfg, "theme", "matrix").unwrap();
+        assert_eq!(
+            config_field_value(&cfg, "theme").as_deref(),
+            Some("\"matrix\"")
+        );
+        assert!(set_config_field(&cfg, "max_rows", "lots").is_err());
+        let cfg = set_config_field(&cfg, "max_rows", "").unwrap();
+        assert_eq!(cfg.max_rows, default_max_rows());
+        let cfg = set_config_field(&cfg, "theme", "").unwrap();
+        assert_eq!(config_field_value(&cfg, "theme"), None);
+    }
+
+    #[test]
+    fn unversioned_config_is_migrated() {
+        let (cfg, from, _) = parse_config("theme = \"dark\"\n").unwrap();
+        assert_eq!(from, Some(0));
+        assert_eq!(cfg.config_version, CONFIG_VERSION);
+        assert_eq!(cfg.theme.as_deref(), Some("dark"));
+
+        let current = format!("config_version = {}\n", CONFIG_VERSION);
+        let (_, from, _) = parse_config(&current).unwrap();
+        assert_eq!(from, None);
+    }
+}
@@ -5,26 +5,90 @@
 use nix::sys::signal::{Signal, kill};
 use nix::unistd::Pid as NixPid;
 use procfs::{process::Stat, process::StatM};
+use std::collections::HashMap;
 use std::ffi::CStr;
+use std::sync::Mutex;
 use std::time::Instant;
-use sysinfo::{CpuExt, PidExt, ProcessExt, System, SystemExt};
+use sysinfo::{CpuExt, DiskExt, PidExt, ProcessExt, System, SystemExt};
 #[allow(dead_code)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct ProcessInfo {
     pub pid: u32,
     pub ppid: Option<u32>,
     pub user: String,
     pub command: String,
-    pub cpu_usage: f32,   // percent
-    pub mem_bytes: u64,   // RSS bytes
-    pub mem_percent: f32, // percent
-    pub virt: u64,        // bytes
-    pub res: u64,         // bytes
-    pub shr: u64,         // bytes (best-effort)
-    pub state: char,      // process state, e.g., 'S', 'R'
+    pub args: Vec<String>, // argv as read from NUL-separated /proc/<pid>/cmdline
+    pub cpu_usage: f32,    // percent
+    pub mem_bytes: u64,    // RSS bytes
+    pub mem_percent: f32,  // percent
+    pub virt: u64,         // bytes
+    pub shr: u64,          // bytes (best-effort)
+    pub state: char,       // process state, e.g., 'S', 'R'
     pub nice: i64,
     pub priority: i64,
-    pub time_total_secs: u64, // utime + stime (seconds)
+    pub time_total_secs: u64,          // utime + stime (seconds)
+    pub user_time_secs: u64,           // utime (seconds spent in userspace)
+    pub sys_time_secs: u64,            // stime (seconds spent in the kernel)
+    pub cgroup: String,                // friendly cgroup/container label, empty when not collected
+    pub disk_read_bytes_per_sec: f64,  // /proc/<pid>/io read_bytes delta (0 when unreadable)
+    pub disk_write_bytes_per_sec: f64, // /proc/<pid>/io write_bytes delta
+}
+
+/// Proportional/private/shared memory of one process, in bytes (from smaps_rollup)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemoryMapSummary {
+    pub rss: u64,
+    pub pss: u64,
+    pub private: u64,
+    pub shared: u64,
+    pub swap: u64,
+}
+
+/// Linux scheduling policy of a process (sched_getscheduler)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SchedPolicy {
+    Other,
+    Fifo,
+    RoundRobin,
+    Batch,
+    Idle,
+    Deadline,
+    Unknown(i32),
+}
+
+impl SchedPolicy {
+    fn from_raw(policy: i32) -> Self {
+        // SCHED_RESET_ON_FORK may be OR'ed into the returned value
+        match policy & !libc::SCHED_RESET_ON_FORK {
+            libc::SCHED_OTHER => SchedPolicy::Other,
+            libc::SCHED_FIFO => SchedPolicy::Fifo,
+            libc::SCHED_RR => SchedPolicy::RoundRobin,
+            libc::SCHED_BATCH => SchedPolicy::Batch,
+            libc::SCHED_IDLE => SchedPolicy::Idle,
+            libc::SCHED_DEADLINE => SchedPolicy::Deadline,
+            other => SchedPolicy::Unknown(other),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            SchedPolicy::Other => "SCHED_OTHER".to_string(),
+            SchedPolicy::Fifo => "SCHED_FIFO".to_string(),
+            SchedPolicy::RoundRobin => "SCHED_RR".to_string(),
+            SchedPolicy::Batch => "SCHED_BATCH".to_string(),
+            SchedPolicy::Idle => "SCHED_IDLE".to_string(),
+            SchedPolicy::Deadline => "SCHED_DEADLINE".to_string(),
+            SchedPolicy::Unknown(n) => format!("unknown ({})", n),
+        }
+    }
+
+    /// Real-time policies preempt every normal process regardless of nice
+    pub fn is_realtime(&self) -> bool {
+        matches!(
+            self,
+            SchedPolicy::Fifo | SchedPolicy::RoundRobin | SchedPolicy::Deadline
+        )
+    }
 }
 
 #[allow(dead_code)]
@@ -32,8 +96,89 @@ pub struct SystemMonitor {
     system: System,
     cpu_count: usize,
     last_net: Option<NetSnapshot>,
+    // CPU sub-sampling: readings are the mean of up to `cpu_samples` refreshes
+    cpu_samples: usize,
+    cpu_acc_global: f32,
+    cpu_acc_cores: Vec<f32>,
+    cpu_acc_n: u32,
+    cpu_avg_global: f32,
+    cpu_avg_cores: Vec<f32>,
+    collect_cgroups: bool,
+    // --cgroup: only processes inside this cgroup path (or below it) are listed
+    cgroup_scope: Option<String>,
+    last_swap: Option<NetSnapshot>,
+    // Per-core thermal_throttle/core_throttle_count from the previous refresh (None = no sysfs file)
+    throttle_counts: Vec<Option<u64>>,
+    throttled_cores: Vec<bool>,
+    commit: Option<CommitInfo>,
+    // UID -> user name; None remembers failed lookups too. Flushed every
+    // USER_CACHE_REFRESHES process refreshes in case accounts are renamed or reassigned.
+    // A Mutex rather than a RefCell keeps the monitor Sync
+    user_cache: Mutex<HashMap<u32, Option<String>>>,
+    user_cache_age: u32,
+    // Per-PID disk I/O counters from the previous process refresh, and the rates derived from them
+    last_io: HashMap<u32, NetSnapshot>,
+    io_rates: HashMap<u32, (f64, f64)>,
+    // Interfaces left out of network rates (loopback by default)
+    skip_ifaces: Vec<String>,
+    // Per-interface counters from the previous get_network_rates_per_iface call
+    last_iface: HashMap<String, NetSnapshot>,
+    // Thermal zone picked for the CPU at startup (None on machines without one)
+    cpu_thermal_zone: Option<std::path::PathBuf>,
+    // Logical CPUs grouped by physical core; empty without SMT or topology info
+    smt_groups: Vec<Vec<usize>>,
+}
+
+const USER_CACHE_REFRESHES: u32 = 60;
+
+/// Committed virtual memory vs the kernel's commit limit (/proc/meminfo), in kB
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CommitInfo {
+    pub committed_kb: u64,
+    pub limit_kb: u64,
+}
+
+impl CommitInfo {
+    /// Committed_AS / CommitLimit in percent; above 100 the system is overcommitted
+    pub fn ratio_percent(&self) -> f64 {
+        self.committed_kb as f64 / self.limit_kb.max(1) as f64 * 100.0
+    }
+}
+
+/// One mounted filesystem, as of the last slow refresh
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiskInfo {
+    pub name: String,
+    pub mount_point: String,
+    pub fs_type: String,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+// Memory-backed filesystems that say nothing about disk space. overlay and squashfs stay
+// visible: they are the root filesystem in containers and every snap mount
+const PSEUDO_FILESYSTEMS: &[&str] = &["tmpfs", "devtmpfs", "ramfs"];
+
+impl DiskInfo {
+    pub fn used_bytes(&self) -> u64 {
+        self.total_bytes.saturating_sub(self.available_bytes)
+    }
+
+    /// Used space in percent of the total (0 for an empty or zero-sized filesystem)
+    pub fn used_percent(&self) -> f64 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            self.used_bytes() as f64 / self.total_bytes as f64 * 100.0
+        }
+    }
+
+    pub fn is_pseudo(&self) -> bool {
+        PSEUDO_FILESYSTEMS.contains(&self.fs_type.as_str())
+    }
 }
 
+// Cumulative counters for rate computation: bytes for the network, pages for swap (in/out)
 #[derive(Clone, Debug)]
 #[allow(dead_code)]
 struct NetSnapshot {
@@ -42,6 +187,15 @@ struct NetSnapshot {
     tx_total: u64,
 }
 
+/// Memory pressure stall information (/proc/pressure/memory), 10s averages in percent
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MemoryPressure {
+    /// Share of time at least one task stalled on memory
+    pub some_avg10: f64,
+    /// Share of time all non-idle tasks stalled on memory at once
+    pub full_avg10: f64,
+}
+
 #[allow(dead_code)]
 impl SystemMonitor {
     pub fn new() -> Self {
@@ -53,18 +207,99 @@ impl SystemMonitor {
             system,
             cpu_count,
             last_net: None,
+            cpu_samples: 1,
+            cpu_acc_global: 0.0,
+            cpu_acc_cores: vec![0.0; cpu_count],
+            cpu_acc_n: 0,
+            cpu_avg_global: 0.0,
+            cpu_avg_cores: vec![0.0; cpu_count],
+            collect_cgroups: false,
+            cgroup_scope: None,
+            last_swap: None,
+            throttle_counts: Vec::new(),
+            throttled_cores: vec![false; cpu_count],
+            commit: None,
+            user_cache: Mutex::new(HashMap::new()),
+            user_cache_age: 0,
+            last_io: HashMap::new(),
+            io_rates: HashMap::new(),
+            skip_ifaces: vec!["lo".to_string()],
+            last_iface: HashMap::new(),
+            cpu_thermal_zone: find_cpu_thermal_zone(),
+            smt_groups: read_smt_groups(cpu_count),
         }
     }
 
+    // One extra /proc read per process, so only when the column is shown
+    pub fn set_collect_cgroups(&mut self, enabled: bool) {
+        self.collect_cgroups = enabled;
+    }
+
+    pub fn set_skipped_interfaces(&mut self, names: &[String]) {
+        self.skip_ifaces = names.to_vec();
+    }
+
+    /// Restrict process listing to one cgroup/slice, e.g. "system.slice/nginx.service"
+    pub fn set_cgroup_scope(&mut self, scope: Option<&str>) {
+        self.cgroup_scope = scope.map(|s| format!("/{}", s.trim_matches('/')));
+    }
+
+    pub fn cgroup_scope(&self) -> Option<&str> {
+        self.cgroup_scope.as_deref()
+    }
+
+    pub fn set_cpu_samples(&mut self, samples: usize) {
+        self.cpu_samples = samples.max(1);
+    }
+
+    // Shortest spacing at which sysinfo produces meaningful CPU deltas
+    pub fn min_cpu_sample_interval() -> std::time::Duration {
+        System::MINIMUM_CPU_UPDATE_INTERVAL
+    }
+
+    /// Take one CPU sub-sample and add it to the running average for the next reading
+    pub fn sample_cpu(&mut self) {
+        self.system.refresh_cpu();
+        self.cpu_acc_global += self.system.global_cpu_info().cpu_usage();
+        for (acc, cpu) in self.cpu_acc_cores.iter_mut().zip(self.system.cpus()) {
+            *acc += cpu.cpu_usage();
+        }
+        self.cpu_acc_n += 1;
+    }
+
+    fn finish_cpu_samples(&mut self) {
+        let n = self.cpu_acc_n.max(1) as f32;
+        self.cpu_avg_global = self.cpu_acc_global / n;
+        for (avg, acc) in self
+            .cpu_avg_cores
+            .iter_mut()
+            .zip(self.cpu_acc_cores.iter_mut())
+        {
+            *avg = *acc / n;
+            *acc = 0.0;
+        }
+        self.cpu_acc_global = 0.0;
+        self.cpu_acc_n = 0;
+    }
+
     pub fn refresh(&mut self) {
         self.refresh_fast();
         self.refresh_slow();
+        self.refresh_processes();
     }
 
     pub fn refresh_fast(&mut self) {
         // Refresh frequent metrics
-        self.system.refresh_cpu();
+        if self.cpu_samples > 1 {
+            self.sample_cpu();
+            self.finish_cpu_samples();
+        } else {
+            self.system.refresh_cpu();
+        }
         self.system.refresh_memory();
+        self.commit = std::fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|s| parse_commit_info(&s));
         self.system.refresh_networks();
         self.system.refresh_system();
         // Network snapshot maintained separately via procfs for cumulative totals
@@ -72,12 +307,124 @@ impl SystemMonitor {
 
     pub fn refresh_slow(&mut self) {
         // Refresh heavier metrics
-        self.system.refresh_processes();
         self.system.refresh_disks_list();
         self.system.refresh_disks();
+        self.refresh_throttle();
+    }
+
+    /// The process table and its per-process extras; the most expensive refresh, so it has
+    /// its own rate (process_rate_ms)
+    pub fn refresh_processes(&mut self) {
+        self.system.refresh_processes();
+        self.refresh_io_rates();
+        self.user_cache_age += 1;
+        if self.user_cache_age >= USER_CACHE_REFRESHES {
+            self.user_cache
+                .get_mut()
+                .unwrap_or_else(|e| e.into_inner())
+                .clear();
+            self.user_cache_age = 0;
+        }
+    }
+
+    // read_bytes/write_bytes count what reached the block layer, unlike rchar/wchar which
+    // include page-cache hits and pipes. Other users' processes are unreadable without root
+    fn refresh_io_rates(&mut self) {
+        let now = Instant::now();
+        let mut seen = HashMap::with_capacity(self.last_io.len());
+        self.io_rates.clear();
+        for pid in self.system.processes().keys() {
+            let pid = pid.as_u32();
+            let Some(io) = procfs::process::Process::new(pid as i32)
+                .ok()
+                .and_then(|p| p.io().ok())
+            else {
+                continue;
+            };
+            let cur = NetSnapshot {
+                ts: now,
+                rx_total: io.read_bytes,
+                tx_total: io.write_bytes,
+            };
+            self.io_rates
+                .insert(pid, compute_rates(self.last_io.get(&pid), &cur));
+            seen.insert(pid, cur);
+        }
+        // Exited PIDs drop out, so a recycled PID starts from a fresh baseline
+        self.last_io = seen;
+    }
+
+    // A core is throttled if its throttle counter moved since the previous refresh
+    fn refresh_throttle(&mut self) {
+        let counts: Vec<Option<u64>> = (0..self.cpu_count)
+            .map(|i| {
+                std::fs::read_to_string(format!(
+                    "/sys/devices/system/cpu/cpu{}/thermal_throttle/core_throttle_count",
+                    i
+                ))
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+            })
+            .collect();
+        self.throttled_cores = counts
+            .iter()
+            .enumerate()
+            .map(
+                |(i, cur)| match (self.throttle_counts.get(i).copied().flatten(), cur) {
+                    (Some(prev), Some(cur)) => *cur > prev,
+                    _ => false,
+                },
+            )
+            .collect();
+        self.throttle_counts = counts;
+    }
+
+    pub fn get_commit_info(&self) -> Option<CommitInfo> {
+        self.commit
+    }
+
+    /// Hyperthread siblings per physical core, ordered by their first logical CPU.
+    /// Empty when the topology is unknown or no core runs more than one thread
+    pub fn get_smt_groups(&self) -> &[Vec<usize>] {
+        &self.smt_groups
+    }
+
+    /// Mounted filesystems by mount point (refreshed by refresh_slow)
+    pub fn get_disks(&self) -> Vec<DiskInfo> {
+        let mut disks: Vec<DiskInfo> = self
+            .system
+            .disks()
+            .iter()
+            .map(|d| DiskInfo {
+                name: d.name().to_string_lossy().into_owned(),
+                mount_point: d.mount_point().to_string_lossy().into_owned(),
+                fs_type: String::from_utf8_lossy(d.file_system()).into_owned(),
+                total_bytes: d.total_space(),
+                available_bytes: d.available_space(),
+            })
+            .collect();
+        disks.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+        disks
+    }
+
+    /// System-wide (open file handles, limit) from /proc/sys/fs/file-nr
+    pub fn get_open_files(&self) -> Option<(u64, u64)> {
+        parse_file_nr(&std::fs::read_to_string("/proc/sys/fs/file-nr").ok()?)
+    }
+
+    /// Whether the kernel exposes throttle counters at all (Intel thermal_throttle)
+    pub fn has_throttle_info(&self) -> bool {
+        self.throttle_counts.iter().any(Option::is_some)
+    }
+
+    pub fn is_core_throttled(&self, core: usize) -> bool {
+        self.throttled_cores.get(core).copied().unwrap_or(false)
     }
 
     pub fn get_global_cpu_usage(&self) -> f64 {
+        if self.cpu_samples > 1 {
+            return self.cpu_avg_global as f64;
+        }
         self.system.global_cpu_info().cpu_usage() as f64
     }
 
@@ -86,6 +433,9 @@ impl SystemMonitor {
     }
 
     pub fn get_cpu_usage_per_core(&self) -> Vec<f32> {
+        if self.cpu_samples > 1 {
+            return self.cpu_avg_cores.clone();
+        }
         self.system
             .cpus()
             .iter()
@@ -108,7 +458,6 @@ impl SystemMonitor {
         }
     }
 
-    #[allow(dead_code)]
     pub fn get_swap_info(&self) -> (u64, u64) {
         let used = self.system.used_swap();
         let total = self.system.total_swap();
@@ -139,23 +488,33 @@ impl SystemMonitor {
         for (pid, proc_) in self.system.processes().iter() {
             let pid_u32 = pid.as_u32();
 
+            if let Some(scope) = &self.cgroup_scope {
+                let inside = std::fs::read_to_string(format!("/proc/{}/cgroup", pid_u32))
+                    .is_ok_and(|c| cgroup_in_scope(cgroup_path_from_file(&c), scope));
+                if !inside {
+                    continue;
+                }
+            }
+
             // Fallback values
             let mut ppid = None;
             let mut virt = 0u64;
-            let mut res = proc_.memory(); // in kB? sysinfo returns kB for memory
-            // sysinfo's memory() returns KB; convert to bytes
-            res *= 1024;
+            let mut statm_resident = None;
             let mut shr = 0u64;
             let mut nice = 0i64;
             let mut priority = 0i64;
             let mut state = 'S';
             let mut time_total_secs = 0u64;
-            let mut command = if proc_.cmd().is_empty() {
+            let mut user_time_secs = 0u64;
+            let mut sys_time_secs = 0u64;
+            let mut args: Vec<String> = proc_.cmd().to_vec();
+            let mut command = if args.is_empty() {
                 proc_.name().to_string()
             } else {
-                proc_.cmd().join(" ")
+                args.join(" ")
             };
             let mut user = String::from("unknown");
+            let mut cgroup = String::new();
 
             // Try procfs for richer details
             if let Ok(procfs_proc) = procfs::process::Process::new(pid_u32 as i32) {
@@ -164,20 +523,20 @@ impl SystemMonitor {
                         &stat,
                         &mut ppid,
                         &mut virt,
-                        &mut res,
-                        &mut shr,
                         &mut nice,
                         &mut priority,
                         &mut state,
                         &mut time_total_secs,
+                        &mut user_time_secs,
+                        &mut sys_time_secs,
                     );
                 }
                 if let Ok(statm) = procfs_proc.statm() {
-                    fill_from_statm(&statm, &mut virt, &mut res, &mut shr);
+                    fill_from_statm(&statm, &mut virt, &mut statm_resident, &mut shr);
                 }
                 if let Ok(status) = procfs_proc.status() {
                     let u = status.ruid;
-                    if let Some(uname) = username_from_uid(u) {
+                    if let Some(uname) = self.cached_username(u) {
                         user = uname;
                     }
                 }
@@ -185,33 +544,58 @@ impl SystemMonitor {
                     && !cmdline.is_empty()
                 {
                     command = cmdline.join(" ");
+                    args = cmdline;
+                }
+                if self.collect_cgroups
+                    && let Ok(content) =
+                        std::fs::read_to_string(format!("/proc/{}/cgroup", pid_u32))
+                {
+                    cgroup = cgroup_label_from_file(&content);
                 }
             }
 
             let cpu_usage = proc_.cpu_usage();
-            let mem_bytes = res;
-            let mem_percent = ((mem_bytes as f64) / (total_mem * 1024u64) as f64 * 100.0) as f32;
+            // Bytes end-to-end; the percentage is taken from the final figure
+            let mem_bytes = resident_bytes(statm_resident, procfs::page_size(), proc_.memory());
+            let (disk_read_bytes_per_sec, disk_write_bytes_per_sec) =
+                self.io_rates.get(&pid_u32).copied().unwrap_or((0.0, 0.0));
+            let mem_percent = (mem_bytes as f64 / total_mem as f64 * 100.0) as f32;
 
             processes.push(ProcessInfo {
                 pid: pid_u32,
                 ppid,
                 user,
                 command,
+                args,
                 cpu_usage,
                 mem_bytes,
                 mem_percent,
                 virt,
-                res,
                 shr,
                 state,
                 nice,
                 priority,
                 time_total_secs,
+                user_time_secs,
+                sys_time_secs,
+                cgroup,
+                disk_read_bytes_per_sec,
+                disk_write_bytes_per_sec,
             });
         }
         processes
     }
 
+    fn cached_username(&self, uid: u32) -> Option<String> {
+        // A poisoned lock only means another lookup panicked; the map itself is still valid
+        self.user_cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(uid)
+            .or_insert_with(|| username_from_uid(uid))
+            .clone()
+    }
+
     pub fn get_top_processes(&self, limit: usize) -> Vec<ProcessInfo> {
         let mut processes = self.get_processes();
         // Sort by CPU usage (descending)
@@ -254,6 +638,25 @@ impl SystemMonitor {
             process_count: self.get_process_count(),
         }
     }
+
+    /// Kernel boot parameters and build string; empty where /proc isn't readable
+    pub fn get_boot_info(&self) -> BootInfo {
+        let read = |path: &str| {
+            std::fs::read_to_string(path)
+                .map(|s| s.trim().to_string())
+                .unwrap_or_default()
+        };
+        BootInfo {
+            cmdline: read("/proc/cmdline"),
+            version: read("/proc/version"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BootInfo {
+    pub cmdline: String,
+    pub version: String,
 }
 
 #[derive(Debug)]
@@ -276,8 +679,7 @@ impl SystemMonitor {
         let mut tx_total: u64 = 0;
         if let Ok(netdev) = procfs::net::dev_status() {
             for (iface, data) in netdev {
-                // skip loopback
-                if iface == "lo" {
+                if self.skip_ifaces.contains(&iface) {
                     continue;
                 }
                 rx_total = rx_total.saturating_add(data.recv_bytes);
@@ -285,60 +687,466 @@ impl SystemMonitor {
             }
         }
 
-        let rates = if let Some(prev) = &self.last_net {
-            let dt = now
-                .saturating_duration_since(prev.ts)
-                .as_secs_f64()
-                .max(0.001);
-            let rx_rate = (rx_total.saturating_sub(prev.rx_total)) as f64 / dt;
-            let tx_rate = (tx_total.saturating_sub(prev.tx_total)) as f64 / dt;
-            (rx_rate, tx_rate)
-        } else {
-            (0.0, 0.0)
-        };
-
-        self.last_net = Some(NetSnapshot {
+        let cur = NetSnapshot {
             ts: now,
             rx_total,
             tx_total,
-        });
+        };
+        let rates = compute_rates(self.last_net.as_ref(), &cur);
+        self.last_net = Some(cur);
         rates
     }
 
-    pub fn nice_increase(&self, pid: u32) -> Result<(), String> {
-        // F8 Nice+
-        // Use libc directly for getpriority/setpriority since nix 0.27 doesn't have them
+    /// (interface, rx bytes/s, tx bytes/s) per interface since the previous call, by name
+    pub fn get_network_rates_per_iface(&mut self) -> Vec<(String, f64, f64)> {
+        let now = Instant::now();
+        let Ok(netdev) = procfs::net::dev_status() else {
+            return Vec::new();
+        };
+        let mut seen = HashMap::with_capacity(netdev.len());
+        let mut rates = Vec::with_capacity(netdev.len());
+        for (iface, data) in netdev {
+            if self.skip_ifaces.contains(&iface) {
+                continue;
+            }
+            let cur = NetSnapshot {
+                ts: now,
+                rx_total: data.recv_bytes,
+                tx_total: data.sent_bytes,
+            };
+            let (rx, tx) = compute_rates(self.last_iface.get(&iface), &cur);
+            rates.push((iface.clone(), rx, tx));
+            seen.insert(iface, cur);
+        }
+        // Interfaces that went away (VPN down) drop out and start fresh if they return
+        self.last_iface = seen;
+        rates.sort_by(|a, b| a.0.cmp(&b.0));
+        rates
+    }
+
+    /// Cumulative (rx, tx) bytes for an interface as of the last per-interface read
+    pub fn get_iface_totals(&self, iface: &str) -> Option<(u64, u64)> {
+        self.last_iface.get(iface).map(|s| (s.rx_total, s.tx_total))
+    }
+
+    /// (swap-in, swap-out) in pages per second since the previous call, from /proc/vmstat
+    pub fn get_swap_rates(&mut self) -> (f64, f64) {
+        let Some((pswpin, pswpout)) = std::fs::read_to_string("/proc/vmstat")
+            .ok()
+            .and_then(|s| parse_vmstat_swap(&s))
+        else {
+            return (0.0, 0.0);
+        };
+        let cur = NetSnapshot {
+            ts: Instant::now(),
+            rx_total: pswpin,
+            tx_total: pswpout,
+        };
+        let rates = compute_rates(self.last_swap.as_ref(), &cur);
+        self.last_swap = Some(cur);
+        rates
+    }
+
+    /// Memory PSI; None on kernels without CONFIG_PSI (or with psi=0)
+    pub fn get_memory_pressure(&self) -> Option<MemoryPressure> {
+        std::fs::read_to_string("/proc/pressure/memory")
+            .ok()
+            .and_then(|s| parse_memory_pressure(&s))
+    }
+
+    pub fn get_nice(&self, pid: u32) -> Result<i32, String> {
+        let id = checked_pid(pid)? as libc::id_t;
+        // Use libc directly for getpriority/setpriority since nix 0.27 doesn't have them.
+        // -1 is a valid nice value, so errno must be cleared first to tell it apart from failure.
         unsafe {
-            let cur = libc::getpriority(libc::PRIO_PROCESS, pid);
-            if libc::getpriority(libc::PRIO_PROCESS, pid) == -1 && *libc::__errno_location() != 0 {
+            *libc::__errno_location() = 0;
+            let cur = libc::getpriority(libc::PRIO_PROCESS, id);
+            if cur == -1 && *libc::__errno_location() != 0 {
                 return Err("Failed to get priority".to_string());
             }
-            let new = cur + 1;
-            if libc::setpriority(libc::PRIO_PROCESS, pid, new) == -1 {
+            Ok(cur)
+        }
+    }
+
+    pub fn set_nice(&self, pid: u32, nice: i32) -> Result<(), String> {
+        let id = checked_pid(pid)? as libc::id_t;
+        unsafe {
+            if libc::setpriority(libc::PRIO_PROCESS, id, nice) == -1 {
                 return Err("Failed to set priority (try running as root)".to_string());
             }
         }
         Ok(())
     }
 
+    pub fn nice_increase(&self, pid: u32) -> Result<(), String> {
+        // F8 Nice+
+        let cur = self.get_nice(pid)?;
+        self.set_nice(pid, cur + 1)
+    }
+
     pub fn nice_decrease(&self, pid: u32) -> Result<(), String> {
         // F7 Nice-
-        unsafe {
-            let cur = libc::getpriority(libc::PRIO_PROCESS, pid);
-            if libc::getpriority(libc::PRIO_PROCESS, pid) == -1 && *libc::__errno_location() != 0 {
-                return Err("Failed to get priority".to_string());
-            }
-            let new = cur - 1;
-            if libc::setpriority(libc::PRIO_PROCESS, pid, new) == -1 {
-                return Err("Failed to set priority (try running as root)".to_string());
+        let cur = self.get_nice(pid)?;
+        self.set_nice(pid, cur - 1)
+    }
+
+    pub fn kill_process(&self, pid: u32) -> Result<(), String> {
+        self.kill_process_with(pid, Signal::SIGTERM)
+    }
+
+    pub fn force_kill_process(&self, pid: u32) -> Result<(), String> {
+        self.kill_process_with(pid, Signal::SIGKILL)
+    }
+
+    pub fn kill_process_with(&self, pid: u32, signal: Signal) -> Result<(), String> {
+        let npid = NixPid::from_raw(checked_pid(pid)?);
+        kill(npid, signal).map_err(format_nix_error)
+    }
+
+    /// Any signal, for the Shift+F9 picker; kill/force_kill are the TERM/KILL shorthands
+    pub fn send_signal(&self, pid: u32, signal: Signal) -> Result<(), String> {
+        self.kill_process_with(pid, signal)
+    }
+
+    pub fn get_memory_map_summary(&self, pid: u32) -> Result<MemoryMapSummary, String> {
+        let pid = checked_pid(pid)?;
+        // smaps_rollup (Linux 4.14+) is pre-summed; full smaps has the same keys per mapping
+        let content = std::fs::read_to_string(format!("/proc/{}/smaps_rollup", pid))
+            .or_else(|_| std::fs::read_to_string(format!("/proc/{}/smaps", pid)))
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::PermissionDenied => {
+                    "Permission denied (try running as root)".to_string()
+                }
+                std::io::ErrorKind::NotFound => "Process not found".to_string(),
+                _ => e.to_string(),
+            })?;
+        Ok(parse_smaps(&content))
+    }
+
+    pub fn get_sched_policy(&self, pid: u32) -> Result<SchedPolicy, String> {
+        let id = checked_pid(pid)?;
+        let policy = unsafe { libc::sched_getscheduler(id) };
+        if policy == -1 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+        Ok(SchedPolicy::from_raw(policy))
+    }
+
+    /// Kernel function a sleeping process is blocked in (/proc/<pid>/wchan); None when it's
+    /// running or the kernel hides the symbol (no permission, or kallsyms restricted)
+    pub fn get_wchan(&self, pid: u32) -> Option<String> {
+        let content = std::fs::read_to_string(format!("/proc/{}/wchan", checked_pid(pid).ok()?));
+        parse_wchan(&content.ok()?)
+    }
+
+    /// CPU temperature in °C from the package/coretemp thermal zone; None without a sensor
+    pub fn get_cpu_temperature(&self) -> Option<f32> {
+        let zone = self.cpu_thermal_zone.as_ref()?;
+        parse_millidegrees(&std::fs::read_to_string(zone.join("temp")).ok()?)
+    }
+
+    /// Working directory of a process (needed to re-run it the way it was started)
+    pub fn get_process_cwd(&self, pid: u32) -> Option<std::path::PathBuf> {
+        std::fs::read_link(format!("/proc/{}/cwd", checked_pid(pid).ok()?)).ok()
+    }
+
+    /// Live PIDs whose argv matches exactly (e.g. a service its supervisor respawned)
+    pub fn find_pids_by_args(&self, args: &[String]) -> Vec<u32> {
+        let Ok(all) = procfs::process::all_processes() else {
+            return Vec::new();
+        };
+        all.filter_map(Result::ok)
+            .filter(|p| p.cmdline().is_ok_and(|c| c == args))
+            .map(|p| p.pid as u32)
+            .collect()
+    }
+
+    pub fn is_process_alive(&self, pid: u32) -> bool {
+        // Signal 0 only checks existence; zombies still answer it, so treat state 'Z' as dead
+        let Ok(raw) = checked_pid(pid) else {
+            return false;
+        };
+        match kill(NixPid::from_raw(raw), None) {
+            Ok(()) | Err(nix::Error::EPERM) => {}
+            Err(_) => return false,
+        }
+        match procfs::process::Process::new(raw).and_then(|p| p.stat()) {
+            Ok(stat) => stat.state != 'Z',
+            Err(_) => true,
+        }
+    }
+}
+
+/// Validate a PID before handing it to kill/setpriority. Linux PIDs are positive and bounded by
+/// pid_max (at most 2^22), so anything that doesn't fit a positive i32 would otherwise wrap into
+/// 0 or a negative value, which kill() interprets as "process group" or "every process".
+fn checked_pid(pid: u32) -> Result<i32, String> {
+    i32::try_from(pid)
+        .ok()
+        .filter(|p| *p > 0)
+        .ok_or_else(|| format!("Invalid PID {}", pid))
+}
+
+/// Pick the unified (v2) hierarchy line from /proc/<pid>/cgroup, else the first v1 line
+fn cgroup_path_from_file(content: &str) -> &str {
+    content
+        .lines()
+        .find_map(|l| l.strip_prefix("0::"))
+        .or_else(|| content.lines().next().and_then(|l| l.splitn(3, ':').nth(2)))
+        .unwrap_or("")
+}
+
+fn cgroup_label_from_file(content: &str) -> String {
+    cgroup_label(cgroup_path_from_file(content))
+}
+
+/// `path` is the scope itself or nested below it ("/a/b" is in "/a", "/ab" is not)
+fn cgroup_in_scope(path: &str, scope: &str) -> bool {
+    scope == "/"
+        || path
+            .strip_prefix(scope)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Turn a cgroup path into a short label: container runtime + id prefix, or the systemd unit
+fn cgroup_label(path: &str) -> String {
+    let path = path.trim();
+    if path.is_empty() || path == "/" {
+        return "-".to_string();
+    }
+    let short_id = |id: &str| id.chars().take(12).collect::<String>();
+    for segment in path.rsplit('/') {
+        let unit = segment.strip_suffix(".scope").unwrap_or(segment);
+        for (prefix, runtime) in [
+            ("docker-", "docker"),
+            ("libpod-", "podman"),
+            ("cri-containerd-", "k8s"),
+            ("crio-", "k8s"),
+        ] {
+            if let Some(id) = unit.strip_prefix(prefix) {
+                return format!("{}:{}", runtime, short_id(id));
             }
         }
-        Ok(())
+        if let Some(name) = segment.strip_prefix("lxc.payload.") {
+            return format!("lxc:{}", name);
+        }
+    }
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    // cgroupfs driver layouts: /docker/<id>, /kubepods/.../<id>
+    match segments.first() {
+        Some(&"docker") if segments.len() > 1 => {
+            return format!("docker:{}", short_id(segments[segments.len() - 1]));
+        }
+        Some(&"kubepods") | Some(&"kubepods.slice") => {
+            return format!("k8s:{}", short_id(segments[segments.len() - 1]));
+        }
+        _ => {}
     }
+    // systemd: the innermost service, else the innermost slice
+    segments
+        .iter()
+        .rev()
+        .find(|s| s.ends_with(".service"))
+        .or_else(|| segments.iter().rev().find(|s| s.ends_with(".slice")))
+        .or(segments.last())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
 
-    pub fn kill_process(&self, pid: u32) -> Result<(), String> {
-        let npid = NixPid::from_raw(pid as i32);
-        kill(npid, Signal::SIGTERM).map_err(format_nix_error)
+/// Sum the kB fields of smaps/smaps_rollup into a byte summary
+fn parse_smaps(content: &str) -> MemoryMapSummary {
+    let mut summary = MemoryMapSummary::default();
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(key), Some(value)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let Ok(kb) = value.parse::<u64>() else {
+            continue;
+        };
+        let bytes = kb.saturating_mul(1024);
+        match key {
+            "Rss:" => summary.rss += bytes,
+            "Pss:" => summary.pss += bytes,
+            "Private_Clean:" | "Private_Dirty:" => summary.private += bytes,
+            "Shared_Clean:" | "Shared_Dirty:" => summary.shared += bytes,
+            "Swap:" => summary.swap += bytes,
+            _ => {}
+        }
+    }
+    summary
+}
+
+/// Bytes/sec between two cumulative counter readings taken `dt` seconds apart.
+/// A counter that went backwards (wrap or interface reset) yields 0 rather than a bogus spike,
+/// and `dt` is clamped so back-to-back samples never divide by zero.
+fn compute_rate(prev: u64, cur: u64, dt: f64) -> f64 {
+    cur.saturating_sub(prev) as f64 / dt.max(0.001)
+}
+
+/// Committed_AS and CommitLimit from /proc/meminfo
+fn parse_commit_info(content: &str) -> Option<CommitInfo> {
+    let field = |name: &str| {
+        content
+            .lines()
+            .find_map(|l| l.strip_prefix(name))?
+            .split_whitespace()
+            .next()?
+            .parse::<u64>()
+            .ok()
+    };
+    Some(CommitInfo {
+        committed_kb: field("Committed_AS:")?,
+        limit_kb: field("CommitLimit:")?,
+    })
+}
+
+// "allocated  unused  max"; unused has been 0 since 2.6, so allocated is what's open
+fn parse_file_nr(content: &str) -> Option<(u64, u64)> {
+    let mut fields = content.split_whitespace().map(|f| f.parse::<u64>().ok());
+    let open = fields.next()??;
+    let max = fields.nth(1)??;
+    Some((open, max))
+}
+
+/// (pswpin, pswpout) cumulative page counts from /proc/vmstat
+fn parse_vmstat_swap(content: &str) -> Option<(u64, u64)> {
+    let mut pswpin = None;
+    let mut pswpout = None;
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next().and_then(|v| v.parse().ok())) {
+            (Some("pswpin"), Some(v)) => pswpin = Some(v),
+            (Some("pswpout"), Some(v)) => pswpout = Some(v),
+            _ => {}
+        }
+    }
+    Some((pswpin?, pswpout?))
+}
+
+/// avg10 of the "some" and "full" lines of a PSI file
+fn parse_memory_pressure(content: &str) -> Option<MemoryPressure> {
+    let avg10 = |kind: &str| {
+        content
+            .lines()
+            .find(|l| l.starts_with(kind))?
+            .split_whitespace()
+            .find_map(|f| f.strip_prefix("avg10="))?
+            .parse::<f64>()
+            .ok()
+    };
+    Some(MemoryPressure {
+        some_avg10: avg10("some")?,
+        full_avg10: avg10("full").unwrap_or(0.0),
+    })
+}
+
+// "0" is what the kernel reports for a runnable task or a symbol it won't reveal
+fn parse_wchan(content: &str) -> Option<String> {
+    let name = content.trim();
+    if name.is_empty() || name == "0" {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+// Prefer the package sensor, then anything CPU-ish, then whatever zone exists
+fn pick_thermal_zone(zones: &[(std::path::PathBuf, String)]) -> Option<std::path::PathBuf> {
+    let rank = |kind: &str| match kind {
+        "x86_pkg_temp" => 0,
+        "coretemp" | "k10temp" => 1,
+        k if k.contains("cpu") || k.contains("soc") => 2,
+        _ => 3,
+    };
+    zones
+        .iter()
+        .min_by_key(|(path, kind)| (rank(kind), path.clone()))
+        .map(|(path, _)| path.clone())
+}
+
+fn find_cpu_thermal_zone() -> Option<std::path::PathBuf> {
+    let zones: Vec<(std::path::PathBuf, String)> = std::fs::read_dir("/sys/class/thermal")
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("thermal_zone"))
+        })
+        .filter_map(|p| {
+            let kind = std::fs::read_to_string(p.join("type")).ok()?;
+            Some((p, kind.trim().to_lowercase()))
+        })
+        .collect();
+    pick_thermal_zone(&zones)
+}
+
+// Kernel cpu lists: "0-3,8,10-11"
+fn parse_cpu_list(content: &str) -> Option<Vec<usize>> {
+    let mut cpus = Vec::new();
+    for part in content.trim().split(',').filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((a, b)) => cpus.extend(a.parse::<usize>().ok()?..=b.parse::<usize>().ok()?),
+            None => cpus.push(part.parse().ok()?),
+        }
+    }
+    Some(cpus)
+}
+
+// Only usable when every logical CPU is accounted for exactly once
+fn group_siblings(lists: &[Vec<usize>], cpu_count: usize) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for list in lists {
+        let mut group = list.clone();
+        group.sort_unstable();
+        if !groups.contains(&group) {
+            groups.push(group);
+        }
+    }
+    groups.sort();
+    let mut seen: Vec<usize> = groups.iter().flatten().copied().collect();
+    seen.sort_unstable();
+    if seen != (0..cpu_count).collect::<Vec<_>>() || groups.iter().all(|g| g.len() < 2) {
+        return Vec::new();
+    }
+    groups
+}
+
+fn read_smt_groups(cpu_count: usize) -> Vec<Vec<usize>> {
+    let lists: Option<Vec<Vec<usize>>> = (0..cpu_count)
+        .map(|cpu| {
+            let path = format!(
+                "/sys/devices/system/cpu/cpu{}/topology/thread_siblings_list",
+                cpu
+            );
+            parse_cpu_list(&std::fs::read_to_string(path).ok()?)
+        })
+        .collect();
+    lists.map_or_else(Vec::new, |l| group_siblings(&l, cpu_count))
+}
+
+// Thermal zones report millidegrees Celsius
+fn parse_millidegrees(content: &str) -> Option<f32> {
+    content
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .map(|m| m as f32 / 1000.0)
+}
+
+/// (rx, tx) rates from the previous snapshot; the first sample has no baseline and reports 0
+fn compute_rates(prev: Option<&NetSnapshot>, cur: &NetSnapshot) -> (f64, f64) {
+    match prev {
+        Some(prev) => {
+            let dt = cur.ts.saturating_duration_since(prev.ts).as_secs_f64();
+            (
+                compute_rate(prev.rx_total, cur.rx_total, dt),
+                compute_rate(prev.tx_total, cur.tx_total, dt),
+            )
+        }
+        None => (0.0, 0.0),
     }
 }
 
@@ -359,12 +1167,12 @@ fn fill_from_stat(
     stat: &Stat,
     ppid: &mut Option<u32>,
     virt: &mut u64,
-    _res: &mut u64,
-    _shr: &mut u64,
     nice: &mut i64,
     priority: &mut i64,
     state: &mut char,
     time_total_secs: &mut u64,
+    user_time_secs: &mut u64,
+    sys_time_secs: &mut u64,
 ) {
     *ppid = Some(stat.ppid as u32);
     *nice = stat.nice;
@@ -373,31 +1181,287 @@ fn fill_from_stat(
     let clk_tck = procfs::ticks_per_second();
     let total_jiffies = stat.utime + stat.stime;
     *time_total_secs = total_jiffies / clk_tck;
+    *user_time_secs = stat.utime / clk_tck;
+    *sys_time_secs = stat.stime / clk_tck;
     // virt/res from statm instead; here set virt as vsize if available
     *virt = stat.vsize;
 }
 
-fn fill_from_statm(statm: &StatM, virt: &mut u64, res: &mut u64, shr: &mut u64) {
+fn fill_from_statm(statm: &StatM, virt: &mut u64, resident: &mut Option<u64>, shr: &mut u64) {
     let page_size = procfs::page_size();
     *virt = statm.size.saturating_mul(page_size);
-    *res = statm.resident.saturating_mul(page_size);
+    *resident = Some(statm.resident);
     *shr = statm.shared.saturating_mul(page_size);
 }
 
+// RSS in bytes: statm pages when procfs answered, else sysinfo's figure (already bytes)
+fn resident_bytes(statm_pages: Option<u64>, page_size: u64, sysinfo_bytes: u64) -> u64 {
+    match statm_pages {
+        Some(pages) => pages.saturating_mul(page_size),
+        None => sysinfo_bytes,
+    }
+}
+
 #[inline]
 fn username_from_uid(uid: u32) -> Option<String> {
-    // Safe wrapper around libc::getpwuid (non-reentrant). For our usage (brief lookup in UI thread)
-    // this is acceptable. If multi-threaded contention becomes an issue, switch to getpwuid_r.
-    unsafe {
-        let pwd = libc::getpwuid(uid as libc::uid_t);
-        if pwd.is_null() {
-            return None;
+    // getpwuid_r fills our own buffer, so concurrent lookups can't clobber a shared static
+    let mut buf = vec![0 as libc::c_char; 1024];
+    loop {
+        let mut pwd: libc::passwd = unsafe { std::mem::zeroed() };
+        let mut result: *mut libc::passwd = std::ptr::null_mut();
+        let rc = unsafe {
+            libc::getpwuid_r(
+                uid as libc::uid_t,
+                &mut pwd,
+                buf.as_mut_ptr(),
+                buf.len(),
+                &mut result,
+            )
+        };
+        // Entries with long gecos/home fields need a bigger buffer
+        if rc == libc::ERANGE && buf.len() < 1 << 16 {
+            buf.resize(buf.len() * 2, 0);
+            continue;
         }
-        let name_ptr = (*pwd).pw_name;
-        if name_ptr.is_null() {
+        if rc != 0 || result.is_null() || pwd.pw_name.is_null() {
             return None;
         }
-        let cstr = CStr::from_ptr(name_ptr);
-        Some(cstr.to_string_lossy().to_string())
+        let cstr = unsafe { CStr::from_ptr(pwd.pw_name) };
+        return Some(cstr.to_string_lossy().to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_checked_pid_rejects_out_of_range() {
+        assert_eq!(checked_pid(1234), Ok(1234));
+        assert!(checked_pid(0).is_err());
+        assert!(checked_pid(u32::MAX).is_err());
+        assert!(checked_pid(i32::MAX as u32 + 1).is_err());
+    }
+
+    #[test]
+    fn test_parse_wchan() {
+        assert_eq!(parse_wchan("do_select"), Some("do_select".to_string()));
+        assert_eq!(parse_wchan("0"), None);
+        assert_eq!(parse_wchan(""), None);
+    }
+
+    #[test]
+    fn test_smt_groups() {
+        assert_eq!(parse_cpu_list("0-2,5\n"), Some(vec![0, 1, 2, 5]));
+        assert_eq!(parse_cpu_list("x"), None);
+        let lists = [vec![0, 2], vec![1, 3], vec![0, 2], vec![1, 3]];
+        assert_eq!(group_siblings(&lists, 4), vec![vec![0, 2], vec![1, 3]]);
+        // No SMT, or a topology that doesn't cover every CPU, means no grouping
+        assert!(group_siblings(&[vec![0], vec![1]], 2).is_empty());
+        assert!(group_siblings(&[vec![0, 1]], 4).is_empty());
+    }
+
+    #[test]
+    fn test_disk_info_usage() {
+        let disk = DiskInfo {
+            name: "/dev/sda1".to_string(),
+            mount_point: "/".to_string(),
+            fs_type: "ext4".to_string(),
+            total_bytes: 1000,
+            available_bytes: 250,
+        };
+        assert_eq!(disk.used_bytes(), 750);
+        assert_eq!(disk.used_percent(), 75.0);
+        assert!(!disk.is_pseudo());
+        let overlay = DiskInfo {
+            fs_type: "overlay".to_string(),
+            ..disk.clone()
+        };
+        assert!(!overlay.is_pseudo());
+        let tmp = DiskInfo {
+            fs_type: "tmpfs".to_string(),
+            total_bytes: 0,
+            ..disk
+        };
+        assert!(tmp.is_pseudo());
+        assert_eq!(tmp.used_percent(), 0.0);
+    }
+
+    #[test]
+    fn test_parse_file_nr() {
+        assert_eq!(
+            parse_file_nr("1344\t0\t9223372036854775807\n"),
+            Some((1344, i64::MAX as u64))
+        );
+        assert_eq!(parse_file_nr("1344"), None);
+    }
+
+    #[test]
+    fn test_thermal_zone_parsing() {
+        assert_eq!(parse_millidegrees("47500\n"), Some(47.5));
+        assert_eq!(parse_millidegrees("garbage"), None);
+        let zone = |n: &str, kind: &str| (std::path::PathBuf::from(n), kind.to_string());
+        let zones = [
+            zone("thermal_zone0", "acpitz"),
+            zone("thermal_zone1", "x86_pkg_temp"),
+        ];
+        assert_eq!(
+            pick_thermal_zone(&zones),
+            Some(std::path::PathBuf::from("thermal_zone1"))
+        );
+        assert_eq!(pick_thermal_zone(&[]), None);
+    }
+
+    #[test]
+    fn test_monitor_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SystemMonitor>();
+    }
+
+    #[test]
+    fn test_username_from_uid() {
+        assert_eq!(username_from_uid(0).as_deref(), Some("root"));
+        assert_eq!(username_from_uid(u32::MAX - 1), None);
+    }
+
+    #[test]
+    fn test_resident_bytes_prefers_statm() {
+        // procfs succeeded: the sysinfo figure must not leak in or be scaled again
+        assert_eq!(resident_bytes(Some(250), 4096, 999), 250 * 4096);
+        assert_eq!(resident_bytes(None, 4096, 999), 999);
+    }
+
+    #[test]
+    fn test_parse_smaps_rollup() {
+        let content = "00400000-7fff0000 ---p 00000000 00:00 0    [rollup]
+Rss:                5000 kB
+Pss:                3000 kB
+Pss_Anon:           1000 kB
+Shared_Clean:       1500 kB
+Shared_Dirty:        500 kB
+Private_Clean:       800 kB
+Private_Dirty:      2200 kB
+Swap:                 10 kB
+";
+        let s = parse_smaps(content);
+        assert_eq!(s.rss, 5000 * 1024);
+        assert_eq!(s.pss, 3000 * 1024);
+        assert_eq!(s.shared, 2000 * 1024);
+        assert_eq!(s.private, 3000 * 1024);
+        assert_eq!(s.swap, 10 * 1024);
+    }
+
+    #[test]
+    fn test_cgroup_label() {
+        assert_eq!(cgroup_label("/"), "-");
+        assert_eq!(
+            cgroup_label("/system.slice/docker-0123456789abcdef0123.scope"),
+            "docker:0123456789ab"
+        );
+        assert_eq!(
+            cgroup_label("/docker/0123456789abcdef0123"),
+            "docker:0123456789ab"
+        );
+        assert_eq!(
+            cgroup_label("/machine.slice/libpod-abcdef0123456789.scope/container"),
+            "podman:abcdef012345"
+        );
+        assert_eq!(cgroup_label("/system.slice/nginx.service"), "nginx.service");
+        assert_eq!(
+            cgroup_label("/user.slice/user-1000.slice/session-2.scope"),
+            "user-1000.slice"
+        );
+        assert_eq!(
+            cgroup_label_from_file("12:cpu:/ignored\n0::/system.slice/sshd.service\n"),
+            "sshd.service"
+        );
+    }
+
+    #[test]
+    fn test_compute_rate_basic() {
+        assert_eq!(compute_rate(1_000, 3_000, 2.0), 1_000.0);
+    }
+
+    #[test]
+    fn test_compute_rate_counter_wrap() {
+        // Counter reset/wrap must not produce a huge bogus rate
+        assert_eq!(compute_rate(u64::MAX - 10, 5, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_compute_rate_zero_dt() {
+        let rate = compute_rate(0, 1, 0.0);
+        assert!(rate.is_finite());
+        assert_eq!(rate, 1_000.0);
+    }
+
+    #[test]
+    fn test_compute_rates_first_sample() {
+        let cur = NetSnapshot {
+            ts: Instant::now(),
+            rx_total: 5_000,
+            tx_total: 7_000,
+        };
+        assert_eq!(compute_rates(None, &cur), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_compute_rates_elapsed() {
+        let start = Instant::now();
+        let prev = NetSnapshot {
+            ts: start,
+            rx_total: 0,
+            tx_total: 100,
+        };
+        let cur = NetSnapshot {
+            ts: start + Duration::from_millis(500),
+            rx_total: 1_000,
+            tx_total: 100,
+        };
+        assert_eq!(compute_rates(Some(&prev), &cur), (2_000.0, 0.0));
+    }
+
+    #[test]
+    fn test_parse_swap_and_pressure() {
+        let vmstat = "nr_free_pages 1000\npswpin 120\npswpout 340\npgfault 9\n";
+        assert_eq!(parse_vmstat_swap(vmstat), Some((120, 340)));
+        assert_eq!(parse_vmstat_swap("nr_free_pages 1000\n"), None);
+
+        let psi = "some avg10=12.50 avg60=3.00 avg300=1.00 total=123\nfull avg10=4.25 avg60=1.00 avg300=0.50 total=45\n";
+        assert_eq!(
+            parse_memory_pressure(psi),
+            Some(MemoryPressure {
+                some_avg10: 12.5,
+                full_avg10: 4.25
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_info() {
+        let meminfo = "MemTotal:       16000000 kB\nCommitLimit:    10000000 kB\nCommitted_AS:   15000000 kB\n";
+        let info = parse_commit_info(meminfo).unwrap();
+        assert_eq!(info.committed_kb, 15_000_000);
+        assert_eq!(info.ratio_percent(), 150.0);
+        assert_eq!(parse_commit_info("MemTotal: 1 kB\n"), None);
+    }
+
+    #[test]
+    fn test_cgroup_in_scope() {
+        let path = cgroup_path_from_file("0::/system.slice/nginx.service\n");
+        assert!(cgroup_in_scope(path, "/system.slice"));
+        assert!(cgroup_in_scope(path, "/system.slice/nginx.service"));
+        assert!(!cgroup_in_scope(path, "/system.slice/nginx"));
+        assert!(!cgroup_in_scope(path, "/user.slice"));
+    }
+
+    #[test]
+    fn test_sched_policy_from_raw() {
+        assert_eq!(SchedPolicy::from_raw(libc::SCHED_OTHER), SchedPolicy::Other);
+        let rr = SchedPolicy::from_raw(libc::SCHED_RR | libc::SCHED_RESET_ON_FORK);
+        assert_eq!(rr, SchedPolicy::RoundRobin);
+        assert!(rr.is_realtime());
+        assert!(!SchedPolicy::Batch.is_realtime());
     }
 }
@@ -13,9 +13,13 @@ use ratatui::{Frame, layout::Rect};
 /// - Safe: Sandboxed execution, resource limits
 /// - Extensible: Multiple plugin types for different purposes
 /// - Future-ready: AsyncTrait support, hot-reload capability
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Plugin execution results
 #[allow(dead_code)]
@@ -74,7 +78,8 @@ pub enum PluginType {
 
 /// Plugin permissions for security control
 #[allow(dead_code)]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Permission {
     ReadSystemMetrics,
     ReadProcessList,
@@ -83,9 +88,95 @@ pub enum Permission {
     ExecuteCommands,
 }
 
-/// Data structure for passing system state to plugins
+/// User security policy: which permissions each plugin may hold
+///
+/// Anything a plugin requests beyond its grant is refused at registration.
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
+pub struct PermissionPolicy {
+    /// Grant for plugins without an explicit entry (read-only by default)
+    pub default_grants: Vec<Permission>,
+    /// Per-plugin grants; an entry replaces the defaults for that plugin
+    pub grants: HashMap<String, Vec<Permission>>,
+    /// Executables a plugin holding `ExecuteCommands` may run
+    pub allowed_commands: HashMap<String, Vec<String>>,
+}
+
+impl Default for PermissionPolicy {
+    fn default() -> Self {
+        // The built-in HTML exporter needs to write its report
+        let mut grants = HashMap::new();
+        grants.insert(
+            "html_exporter".to_string(),
+            vec![
+                Permission::ReadSystemMetrics,
+                Permission::ReadProcessList,
+                Permission::WriteFiles,
+            ],
+        );
+        Self {
+            default_grants: vec![Permission::ReadSystemMetrics, Permission::ReadProcessList],
+            grants,
+            allowed_commands: HashMap::new(),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl PermissionPolicy {
+    /// Permissions granted to the named plugin
+    pub fn granted(&self, plugin: &str) -> &[Permission] {
+        self.grants
+            .get(plugin)
+            .map(Vec::as_slice)
+            .unwrap_or(&self.default_grants)
+    }
+
+    /// Whether `command` (program plus args) is on the plugin's allowlist
+    pub fn is_command_allowed(&self, plugin: &str, command: &str) -> bool {
+        if !self.granted(plugin).contains(&Permission::ExecuteCommands) {
+            return false;
+        }
+        let Some(program) = command.split_whitespace().next() else {
+            return false;
+        };
+        self.allowed_commands
+            .get(plugin)
+            .is_some_and(|list| list.iter().any(|c| c == program))
+    }
+}
+
+/// On-disk plugin configuration (`[security]` policy plus `[plugins.<name>]` settings)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct PluginConfigFile {
+    security: SecuritySection,
+    plugins: HashMap<String, toml::Table>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct SecuritySection {
+    default: Vec<Permission>,
+    grants: HashMap<String, Vec<Permission>>,
+    commands: HashMap<String, Vec<String>>,
+}
+
+impl Default for SecuritySection {
+    fn default() -> Self {
+        let policy = PermissionPolicy::default();
+        Self {
+            default: policy.default_grants,
+            // Merged over the built-in grants by load_config_str
+            grants: HashMap::new(),
+            commands: policy.allowed_commands,
+        }
+    }
+}
+
+/// Data structure for passing system state to plugins
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
 pub struct SystemSnapshot {
     pub cpu_usage: f64,
     pub memory_usage: f64,
@@ -94,7 +185,22 @@ pub struct SystemSnapshot {
     pub process_count: usize,
     pub uptime_seconds: u64,
     pub load_average: (f64, f64, f64),
+    /// CPU package temperature in °C, None when the machine exposes no sensor
+    pub cpu_temperature: Option<f32>,
     pub timestamp: u64,
+    /// Heaviest processes by CPU (filled for exports, empty on the per-tick pipeline)
+    pub top_processes: Vec<ProcessSummary>,
+}
+
+/// Process row carried in a snapshot
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct ProcessSummary {
+    pub pid: u32,
+    pub user: String,
+    pub command: String,
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
 }
 
 /// Widget plugin trait for custom TUI components
@@ -133,6 +239,8 @@ pub trait DataProcessorPlugin: Send + Sync {
 
 /// Monitoring source plugin for additional metrics
 #[allow(dead_code)]
+// async_trait marks the boxed future #[must_use] on top of Future's own attribute
+#[allow(clippy::double_must_use)]
 #[async_trait::async_trait]
 pub trait MonitoringSourcePlugin: Send + Sync {
     fn info(&self) -> PluginInfo;
@@ -155,9 +263,16 @@ pub trait ExporterPlugin: Send + Sync {
 pub struct PluginManager {
     widget_plugins: Vec<Box<dyn WidgetPlugin>>,
     processor_plugins: Vec<Box<dyn DataProcessorPlugin>>,
-    monitoring_plugins: Vec<Box<dyn MonitoringSourcePlugin>>,
-    export_plugins: Vec<Box<dyn ExporterPlugin>>,
+    // Shared so a metrics collection can run on its own task while the UI keeps drawing
+    monitoring_plugins: Vec<Arc<dyn MonitoringSourcePlugin>>,
+    // Shared so exports can be written on a blocking task
+    export_plugins: Vec<Arc<dyn ExporterPlugin>>,
     plugin_configs: HashMap<String, HashMap<String, String>>,
+    policy: PermissionPolicy,
+    // Where the config came from, so reloads can re-read it
+    config_path: Option<String>,
+    // Last update/collection per plugin name, for `update_interval` throttling
+    last_run: HashMap<String, Instant>,
 }
 
 #[allow(dead_code)]
@@ -169,13 +284,207 @@ impl PluginManager {
             monitoring_plugins: Vec::new(),
             export_plugins: Vec::new(),
             plugin_configs: HashMap::new(),
+            policy: PermissionPolicy::default(),
+            config_path: None,
+            last_run: HashMap::new(),
         }
     }
 
+    /// Per-plugin `update_interval` (ms) from its config; None = every refresh
+    fn update_interval(&self, name: &str) -> Option<Duration> {
+        self.plugin_configs
+            .get(name)?
+            .get("update_interval")?
+            .parse::<u64>()
+            .ok()
+            .filter(|&ms| ms > 0)
+            .map(Duration::from_millis)
+    }
+
+    /// Whether the plugin's interval has elapsed; records the run when it has
+    fn take_due(&mut self, name: &str, now: Instant) -> bool {
+        if let (Some(interval), Some(last)) = (self.update_interval(name), self.last_run.get(name))
+            && now.saturating_duration_since(*last) < interval
+        {
+            return false;
+        }
+        self.last_run.insert(name.to_string(), now);
+        true
+    }
+
+    /// Replace the security policy used to vet plugin registrations
+    pub fn set_policy(&mut self, policy: PermissionPolicy) {
+        self.policy = policy;
+    }
+
+    pub fn policy(&self) -> &PermissionPolicy {
+        &self.policy
+    }
+
     /// Load a plugin configuration from TOML file
-    pub fn load_config(&mut self, _config_path: &str) -> PluginResult<()> {
-        // TODO: Implement TOML config parsing
-        // This would load plugin definitions, permissions, and settings
+    pub fn load_config(&mut self, config_path: &str) -> PluginResult<()> {
+        let content = std::fs::read_to_string(config_path)
+            .map_err(|e| PluginError::LoadFailed(format!("{}: {}", config_path, e)))?;
+        self.load_config_str(&content)?;
+        self.config_path = Some(config_path.to_string());
+        Ok(())
+    }
+
+    /// Re-read the config file loaded earlier (no-op when none was loaded)
+    fn reread_config(&mut self) -> PluginResult<()> {
+        match self.config_path.clone() {
+            Some(path) => self.load_config(&path),
+            None => Ok(()),
+        }
+    }
+
+    /// Names of all registered plugins, in registration order per type
+    pub fn plugin_names(&self) -> Vec<String> {
+        self.plugin_infos()
+            .into_iter()
+            .map(|info| info.name)
+            .collect()
+    }
+
+    fn plugin_infos(&self) -> Vec<PluginInfo> {
+        self.widget_plugins
+            .iter()
+            .map(|p| p.info())
+            .chain(self.processor_plugins.iter().map(|p| p.info()))
+            .chain(self.monitoring_plugins.iter().map(|p| p.info()))
+            .chain(self.export_plugins.iter().map(|p| p.info()))
+            .collect()
+    }
+
+    /// Hot-reload one plugin: re-read the config, shut it down and initialize it again
+    ///
+    /// Built-in plugins are simply re-initialized. The policy is re-checked first and a plugin
+    /// that no longer passes is unregistered, so a tightened grant takes effect without a restart.
+    pub fn reload(&mut self, name: &str) -> PluginResult<()> {
+        self.reread_config()?;
+        self.reinitialize(name)
+    }
+
+    /// Hot-reload every registered plugin from a single read of the config
+    ///
+    /// Returns how many were reloaded and the errors of those that weren't; one failing
+    /// plugin doesn't stop the rest.
+    pub fn reload_all(&mut self) -> PluginResult<(usize, Vec<PluginError>)> {
+        self.reread_config()?;
+        let mut reloaded = 0;
+        let mut failed = Vec::new();
+        for name in self.plugin_names() {
+            match self.reinitialize(&name) {
+                Ok(()) => reloaded += 1,
+                Err(e) => failed.push(e),
+            }
+        }
+        Ok((reloaded, failed))
+    }
+
+    // Re-check the policy and re-initialize from the already loaded config
+    fn reinitialize(&mut self, name: &str) -> PluginResult<()> {
+        let infos: Vec<PluginInfo> = self
+            .plugin_infos()
+            .into_iter()
+            .filter(|info| info.name == name)
+            .collect();
+        if infos.is_empty() {
+            return Err(PluginError::LoadFailed(format!(
+                "Plugin '{}' not found",
+                name
+            )));
+        }
+        if let Some(err) = infos
+            .iter()
+            .find_map(|info| Self::check_policy(&self.policy, info).err())
+        {
+            self.unregister(name);
+            return Err(err);
+        }
+
+        let config = self.plugin_configs.get(name).cloned().unwrap_or_default();
+        for plugin in &mut self.widget_plugins {
+            if plugin.info().name == name {
+                plugin.shutdown()?;
+                plugin.initialize(&config)?;
+            }
+        }
+        for plugin in &mut self.processor_plugins {
+            if plugin.info().name == name {
+                plugin.initialize(&config)?;
+            }
+        }
+        for plugin in &mut self.monitoring_plugins {
+            if plugin.info().name == name {
+                exclusive(plugin, name)?.initialize(&config)?;
+            }
+        }
+        for plugin in &mut self.export_plugins {
+            if plugin.info().name == name {
+                exclusive(plugin, name)?.initialize(&config)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop every plugin registered under `name` so it is no longer updated or used
+    fn unregister(&mut self, name: &str) {
+        for plugin in &mut self.widget_plugins {
+            if plugin.info().name == name {
+                // Dropped either way; a failing shutdown has nothing left to clean up
+                let _ = plugin.shutdown();
+            }
+        }
+        self.widget_plugins.retain(|p| p.info().name != name);
+        self.processor_plugins.retain(|p| p.info().name != name);
+        self.monitoring_plugins.retain(|p| p.info().name != name);
+        self.export_plugins.retain(|p| p.info().name != name);
+        self.last_run.remove(name);
+    }
+
+    /// Apply plugin settings and the security policy from TOML text
+    ///
+    /// ```toml
+    /// [security]
+    /// default = ["read_system_metrics"]
+    /// [security.grants]
+    /// runner = ["read_system_metrics", "execute_commands"]
+    /// [security.commands]
+    /// runner = ["sensors"]
+    /// [plugins.runner]
+    /// update_interval = 1000
+    /// ```
+    pub fn load_config_str(&mut self, content: &str) -> PluginResult<()> {
+        let file: PluginConfigFile =
+            toml::from_str(content).map_err(|e| PluginError::InvalidConfig(e.to_string()))?;
+        // User entries replace the built-in grant for the same plugin and leave the rest alone,
+        // so a [security.grants] table doesn't revoke what the bundled exporter needs
+        let mut grants = PermissionPolicy::default().grants;
+        grants.extend(file.security.grants);
+        self.policy = PermissionPolicy {
+            default_grants: file.security.default,
+            grants,
+            allowed_commands: file.security.commands,
+        };
+        // Plugins take flat string settings; render scalars without TOML quoting
+        self.plugin_configs = file
+            .plugins
+            .into_iter()
+            .map(|(name, table)| {
+                let settings = table
+                    .into_iter()
+                    .map(|(k, v)| {
+                        let v = match v {
+                            toml::Value::String(s) => s,
+                            other => other.to_string(),
+                        };
+                        (k, v)
+                    })
+                    .collect();
+                (name, settings)
+            })
+            .collect();
         Ok(())
     }
 
@@ -208,7 +517,7 @@ impl PluginManager {
     ) -> PluginResult<()> {
         let info = plugin.info();
         self.validate_permissions(&info)?;
-        self.monitoring_plugins.push(plugin);
+        self.monitoring_plugins.push(Arc::from(plugin));
         Ok(())
     }
 
@@ -216,7 +525,7 @@ impl PluginManager {
     pub fn register_export_plugin(&mut self, plugin: Box<dyn ExporterPlugin>) -> PluginResult<()> {
         let info = plugin.info();
         self.validate_permissions(&info)?;
-        self.export_plugins.push(plugin);
+        self.export_plugins.push(Arc::from(plugin));
         Ok(())
     }
 
@@ -237,13 +546,13 @@ impl PluginManager {
         for plugin in &mut self.monitoring_plugins {
             let name = plugin.info().name.clone();
             let config = self.plugin_configs.get(&name).cloned().unwrap_or_default();
-            plugin.initialize(&config)?;
+            exclusive(plugin, &name)?.initialize(&config)?;
         }
 
         for plugin in &mut self.export_plugins {
             let name = plugin.info().name.clone();
             let config = self.plugin_configs.get(&name).cloned().unwrap_or_default();
-            plugin.initialize(&config)?;
+            exclusive(plugin, &name)?.initialize(&config)?;
         }
 
         Ok(())
@@ -251,12 +560,19 @@ impl PluginManager {
 
     /// Update all plugins with new system data
     pub fn update_plugins(&mut self, snapshot: &SystemSnapshot) -> PluginResult<()> {
-        // Update widget plugins
-        for plugin in &mut self.widget_plugins {
+        // Update widget plugins that are due (honoring their update_interval)
+        let now = Instant::now();
+        let mut widgets = std::mem::take(&mut self.widget_plugins);
+        for plugin in &mut widgets {
+            let name = plugin.info().name;
+            if !self.take_due(&name, now) {
+                continue;
+            }
             if let Err(e) = plugin.update(snapshot) {
-                eprintln!("Plugin {} update failed: {}", plugin.info().name, e);
+                eprintln!("Plugin {} update failed: {}", name, e);
             }
         }
+        self.widget_plugins = widgets;
 
         Ok(())
     }
@@ -299,6 +615,50 @@ impl PluginManager {
         metrics
     }
 
+    /// Detached metrics collection over the monitoring plugins that are due, suitable for
+    /// `tokio::spawn`
+    ///
+    /// Yields the merged metrics plus one message per plugin that failed. Plugins still inside
+    /// their `update_interval` are skipped, so callers should merge rather than replace.
+    pub fn metrics_collector(
+        &mut self,
+    ) -> impl Future<Output = (HashMap<String, f64>, Vec<String>)> + Send + 'static {
+        let now = Instant::now();
+        let all = self.monitoring_plugins.clone();
+        let plugins: Vec<_> = all
+            .into_iter()
+            .filter(|p| self.take_due(&p.info().name, now))
+            .collect();
+        async move {
+            let mut metrics = HashMap::new();
+            let mut errors = Vec::new();
+            for plugin in &plugins {
+                match plugin.collect_metrics().await {
+                    Ok(plugin_metrics) => metrics.extend(plugin_metrics),
+                    Err(e) => errors.push(format!("{}: {}", plugin.info().name, e)),
+                }
+            }
+            (metrics, errors)
+        }
+    }
+
+    pub fn has_monitoring_plugins(&self) -> bool {
+        !self.monitoring_plugins.is_empty()
+    }
+
+    /// (plugin name, format) for every format offered by a registered exporter
+    pub fn exporter_formats(&self) -> Vec<(String, String)> {
+        self.export_plugins
+            .iter()
+            .flat_map(|p| {
+                let name = p.info().name;
+                p.supported_formats()
+                    .into_iter()
+                    .map(move |fmt| (name.clone(), fmt))
+            })
+            .collect()
+    }
+
     /// Export data using specified plugin
     pub fn export_with_plugin(
         &self,
@@ -306,16 +666,18 @@ impl PluginManager {
         snapshot: &SystemSnapshot,
         filepath: &str,
     ) -> PluginResult<()> {
-        for plugin in &self.export_plugins {
-            if plugin.info().name == plugin_name {
-                return plugin.export(snapshot, filepath);
-            }
-        }
+        self.exporter(plugin_name)?.export(snapshot, filepath)
+    }
 
-        Err(PluginError::LoadFailed(format!(
-            "Export plugin '{}' not found",
-            plugin_name
-        )))
+    /// Shared handle to a named exporter, for running an export off the UI task
+    pub fn exporter(&self, plugin_name: &str) -> PluginResult<Arc<dyn ExporterPlugin>> {
+        self.export_plugins
+            .iter()
+            .find(|p| p.info().name == plugin_name)
+            .cloned()
+            .ok_or_else(|| {
+                PluginError::LoadFailed(format!("Export plugin '{}' not found", plugin_name))
+            })
     }
 
     /// Shutdown all plugins gracefully
@@ -329,17 +691,42 @@ impl PluginManager {
         Ok(())
     }
 
-    /// Validate plugin permissions against system policy
-    fn validate_permissions(&self, _info: &PluginInfo) -> PluginResult<()> {
-        // TODO: Implement permission validation logic
-        // This would check against system security policy
-        // and user-defined plugin restrictions
+    /// Validate plugin permissions against the user's policy
+    fn validate_permissions(&self, info: &PluginInfo) -> PluginResult<()> {
+        Self::check_policy(&self.policy, info)
+    }
 
-        // For now, allow all permissions (development mode)
+    // Split from validate_permissions so reload can check while plugins are mutably borrowed
+    fn check_policy(policy: &PermissionPolicy, info: &PluginInfo) -> PluginResult<()> {
+        let granted = policy.granted(&info.name);
+        if let Some(denied) = info.permissions.iter().find(|p| !granted.contains(p)) {
+            return Err(PluginError::PermissionDenied(format!(
+                "plugin '{}' requests {:?}, which the policy does not grant",
+                info.name, denied
+            )));
+        }
+        // A command grant without an allowlist would be a blank cheque
+        if info.permissions.contains(&Permission::ExecuteCommands)
+            && policy
+                .allowed_commands
+                .get(&info.name)
+                .is_none_or(|list| list.is_empty())
+        {
+            return Err(PluginError::PermissionDenied(format!(
+                "plugin '{}' requests ExecuteCommands but has no allowed commands",
+                info.name
+            )));
+        }
         Ok(())
     }
 }
 
+// Mutable access to a shared plugin; fails while a collection/export task still holds it
+fn exclusive<'a, T: ?Sized>(plugin: &'a mut Arc<T>, name: &str) -> PluginResult<&'a mut T> {
+    Arc::get_mut(plugin)
+        .ok_or_else(|| PluginError::RuntimeError(format!("Plugin '{}' is busy, try again", name)))
+}
+
 impl Default for PluginManager {
     fn default() -> Self {
         Self::new()
@@ -350,7 +737,7 @@ impl Default for PluginManager {
 #[allow(dead_code)]
 pub struct CpuTempWidgetPlugin {
     name: String,
-    temperature: f64,
+    temperature: Option<f64>,
 }
 
 #[allow(dead_code)]
@@ -358,7 +745,7 @@ impl CpuTempWidgetPlugin {
     pub fn new() -> Self {
         Self {
             name: "CPU Temperature Monitor".to_string(),
-            temperature: 0.0,
+            temperature: None,
         }
     }
 }
@@ -380,10 +767,8 @@ impl WidgetPlugin for CpuTempWidgetPlugin {
         Ok(())
     }
 
-    fn update(&mut self, _snapshot: &SystemSnapshot) -> PluginResult<()> {
-        // TODO: Read actual CPU temperature from /sys/class/thermal/
-        // For demo, simulate temperature
-        self.temperature = 45.0 + (rand::random::<f64>() * 20.0);
+    fn update(&mut self, snapshot: &SystemSnapshot) -> PluginResult<()> {
+        self.temperature = snapshot.cpu_temperature.map(f64::from);
         Ok(())
     }
 
@@ -393,24 +778,185 @@ impl WidgetPlugin for CpuTempWidgetPlugin {
             widgets::{Block, Borders, Gauge},
         };
 
-        let color = if self.temperature > 80.0 {
+        let block = Block::default().borders(Borders::ALL).title("CPU Temp");
+        let Some(temperature) = self.temperature else {
+            frame.render_widget(Gauge::default().block(block).label("no sensor"), area);
+            return;
+        };
+
+        let color = if temperature > 80.0 {
             Color::Red
-        } else if self.temperature > 65.0 {
+        } else if temperature > 65.0 {
             Color::Yellow
         } else {
             Color::Green
         };
 
         let gauge = Gauge::default()
-            .block(Block::default().borders(Borders::ALL).title("CPU Temp"))
+            .block(block)
             .gauge_style(Style::default().fg(color))
-            .percent((self.temperature.min(100.0)) as u16)
-            .label(format!("{:.1}°C", self.temperature));
+            .percent(temperature.clamp(0.0, 100.0) as u16)
+            .label(format!("{:.1}°C", temperature));
 
         frame.render_widget(gauge, area);
     }
 }
 
+/// Built-in exporter writing a self-contained HTML report (inline CSS and SVG, no assets)
+pub struct HtmlExporterPlugin {
+    title: String,
+}
+
+impl HtmlExporterPlugin {
+    pub fn new() -> Self {
+        Self {
+            title: "Lyvoxa System Snapshot".to_string(),
+        }
+    }
+
+    fn render_html(&self, snapshot: &SystemSnapshot) -> String {
+        let mut rows = String::new();
+        for p in &snapshot.top_processes {
+            rows.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td class=\"num\">{:.1}%</td><td class=\"num\">{}</td><td class=\"cmd\">{}</td></tr>\n",
+                p.pid,
+                html_escape(&p.user),
+                p.cpu_percent,
+                humansize::format_size(p.memory_bytes, humansize::DECIMAL),
+                html_escape(&p.command)
+            ));
+        }
+        if rows.is_empty() {
+            rows.push_str("<tr><td colspan=\"5\">No process data</td></tr>\n");
+        }
+        let (l1, l5, l15) = snapshot.load_average;
+        let taken = chrono::DateTime::from_timestamp(snapshot.timestamp as i64, 0)
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+            .unwrap_or_default();
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ background: #05081a; color: #c8d2ff; font-family: sans-serif; margin: 2em; }}
+h1 {{ color: #7864ff; }}
+.gauges {{ display: flex; gap: 2em; }}
+.gauge text {{ fill: #c8d2ff; font-size: 14px; }}
+table {{ border-collapse: collapse; width: 100%; margin-top: 1.5em; }}
+th, td {{ border-bottom: 1px solid #1e2337; padding: 0.4em 0.8em; text-align: left; }}
+th {{ color: #8c78ff; }}
+td.num {{ text-align: right; font-variant-numeric: tabular-nums; }}
+td.cmd {{ font-family: monospace; word-break: break-all; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<p>Taken {taken} &middot; Uptime {up_h}h {up_m:02}m &middot; {count} processes &middot; Load {l1:.2} {l5:.2} {l15:.2}</p>
+<div class="gauges">
+{cpu_gauge}
+{mem_gauge}
+</div>
+<p>Network: RX {rx}/s &middot; TX {tx}/s</p>
+<table>
+<tr><th>PID</th><th>User</th><th>CPU</th><th>Memory</th><th>Command</th></tr>
+{rows}</table>
+</body>
+</html>
+"#,
+            title = html_escape(&self.title),
+            taken = taken,
+            up_h = snapshot.uptime_seconds / 3600,
+            up_m = (snapshot.uptime_seconds / 60) % 60,
+            count = snapshot.process_count,
+            l1 = l1,
+            l5 = l5,
+            l15 = l15,
+            cpu_gauge = svg_gauge("CPU", snapshot.cpu_usage),
+            mem_gauge = svg_gauge("Memory", snapshot.memory_usage),
+            rx = humansize::format_size(snapshot.network_rx.max(0.0) as u64, humansize::DECIMAL),
+            tx = humansize::format_size(snapshot.network_tx.max(0.0) as u64, humansize::DECIMAL),
+            rows = rows
+        )
+    }
+}
+
+impl ExporterPlugin for HtmlExporterPlugin {
+    fn info(&self) -> PluginInfo {
+        PluginInfo {
+            name: "html_exporter".to_string(),
+            version: "1.0.0".to_string(),
+            description: "Self-contained HTML report with gauges and a process table".to_string(),
+            author: "Lyvoxa Team".to_string(),
+            plugin_type: PluginType::Exporter,
+            permissions: vec![
+                Permission::ReadSystemMetrics,
+                Permission::ReadProcessList,
+                Permission::WriteFiles,
+            ],
+        }
+    }
+
+    fn initialize(&mut self, config: &HashMap<String, String>) -> PluginResult<()> {
+        if let Some(title) = config.get("title") {
+            self.title = title.clone();
+        }
+        Ok(())
+    }
+
+    fn export(&self, snapshot: &SystemSnapshot, filepath: &str) -> PluginResult<()> {
+        std::fs::write(filepath, self.render_html(snapshot))
+            .map_err(|e| PluginError::RuntimeError(format!("{}: {}", filepath, e)))
+    }
+
+    fn supported_formats(&self) -> Vec<String> {
+        vec!["html".to_string()]
+    }
+}
+
+// Half-circle gauge drawn as an SVG arc, colored by severity
+fn svg_gauge(label: &str, percent: f64) -> String {
+    let pct = percent.clamp(0.0, 100.0);
+    let angle = std::f64::consts::PI * (1.0 - pct / 100.0);
+    let (x, y) = (60.0 + 50.0 * angle.cos(), 60.0 - 50.0 * angle.sin());
+    let color = if pct >= 90.0 {
+        "#ff5064"
+    } else if pct >= 75.0 {
+        "#ffbe50"
+    } else {
+        "#64ff96"
+    };
+    format!(
+        r##"<svg class="gauge" width="120" height="80" viewBox="0 0 120 80">
+<path d="M10 60 A50 50 0 0 1 110 60" fill="none" stroke="#1e2337" stroke-width="10"/>
+<path d="M10 60 A50 50 0 0 1 {x:.1} {y:.1}" fill="none" stroke="{color}" stroke-width="10"/>
+<text x="60" y="58" text-anchor="middle">{pct:.1}%</text>
+<text x="60" y="76" text-anchor="middle">{label}</text>
+</svg>"##,
+        x = x,
+        y = y,
+        color = color,
+        pct = pct,
+        label = html_escape(label)
+    )
+}
+
+fn html_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
 /// Plugin development utilities
 pub mod dev_utils {
     use super::*;
@@ -442,6 +988,231 @@ mod tests {
         assert_eq!(manager.widget_plugins.len(), 0);
     }
 
+    struct CommandPlugin;
+
+    impl DataProcessorPlugin for CommandPlugin {
+        fn info(&self) -> PluginInfo {
+            PluginInfo {
+                name: "runner".to_string(),
+                version: "0.1.0".to_string(),
+                description: "test plugin running commands".to_string(),
+                author: "test".to_string(),
+                plugin_type: PluginType::DataProcessor,
+                permissions: vec![Permission::ReadSystemMetrics, Permission::ExecuteCommands],
+            }
+        }
+
+        fn initialize(&mut self, _config: &HashMap<String, String>) -> PluginResult<()> {
+            Ok(())
+        }
+
+        fn process(&self, snapshot: &SystemSnapshot) -> PluginResult<SystemSnapshot> {
+            Ok(snapshot.clone())
+        }
+    }
+
+    #[test]
+    fn test_command_allowlist() {
+        let mut manager = PluginManager::new();
+        // Default policy is read-only
+        assert!(matches!(
+            manager.register_processor_plugin(Box::new(CommandPlugin)),
+            Err(PluginError::PermissionDenied(_))
+        ));
+
+        // Granted but no allowlist: still refused
+        let mut policy = PermissionPolicy::default();
+        policy.grants.insert(
+            "runner".to_string(),
+            vec![Permission::ReadSystemMetrics, Permission::ExecuteCommands],
+        );
+        manager.set_policy(policy.clone());
+        assert!(
+            manager
+                .register_processor_plugin(Box::new(CommandPlugin))
+                .is_err()
+        );
+
+        policy
+            .allowed_commands
+            .insert("runner".to_string(), vec!["sensors".to_string()]);
+        manager.set_policy(policy);
+        assert!(
+            manager
+                .register_processor_plugin(Box::new(CommandPlugin))
+                .is_ok()
+        );
+        assert!(manager.policy().is_command_allowed("runner", "sensors -j"));
+        assert!(!manager.policy().is_command_allowed("runner", "rm -rf /"));
+        assert!(!manager.policy().is_command_allowed("other", "sensors"));
+    }
+
+    #[test]
+    fn test_policy_file_denies_execute_commands() {
+        let mut manager = PluginManager::new();
+        manager
+            .load_config_str(
+                r#"
+[security.grants]
+runner = ["read_system_metrics"]
+
+[plugins.runner]
+update_interval = 500
+"#,
+            )
+            .unwrap();
+        match manager.register_processor_plugin(Box::new(CommandPlugin)) {
+            Err(PluginError::PermissionDenied(msg)) => assert!(msg.contains("ExecuteCommands")),
+            other => panic!("expected PermissionDenied, got {:?}", other.err()),
+        }
+        assert_eq!(manager.plugin_configs["runner"]["update_interval"], "500");
+        // Granting one plugin must not revoke the built-in exporter's grant
+        assert!(
+            manager
+                .register_export_plugin(Box::new(HtmlExporterPlugin::new()))
+                .is_ok()
+        );
+
+        manager
+            .load_config_str(
+                r#"
+[security.grants]
+runner = ["read_system_metrics", "execute_commands"]
+[security.commands]
+runner = ["sensors"]
+"#,
+            )
+            .unwrap();
+        assert!(
+            manager
+                .register_processor_plugin(Box::new(CommandPlugin))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_policy_file_rejects_unknown_permission() {
+        let mut manager = PluginManager::new();
+        let res = manager.load_config_str("[security]\ndefault = [\"root_shell\"]\n");
+        assert!(matches!(res, Err(PluginError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_reload() {
+        let mut manager = PluginManager::new();
+        manager
+            .register_widget_plugin(Box::new(CpuTempWidgetPlugin::new()))
+            .unwrap();
+        assert!(manager.reload("cpu_temp_widget").is_ok());
+        assert!(manager.reload("missing").is_err());
+        let (reloaded, failed) = manager.reload_all().unwrap();
+        assert_eq!(reloaded, 1);
+        assert!(failed.is_empty());
+    }
+
+    #[test]
+    fn test_reload_unregisters_plugin_after_tightened_grant() {
+        let mut manager = PluginManager::new();
+        let mut policy = PermissionPolicy::default();
+        policy.grants.insert(
+            "runner".to_string(),
+            vec![Permission::ReadSystemMetrics, Permission::ExecuteCommands],
+        );
+        policy
+            .allowed_commands
+            .insert("runner".to_string(), vec!["sensors".to_string()]);
+        manager.set_policy(policy);
+        manager
+            .register_processor_plugin(Box::new(CommandPlugin))
+            .unwrap();
+        manager
+            .register_widget_plugin(Box::new(CpuTempWidgetPlugin::new()))
+            .unwrap();
+        manager.set_policy(PermissionPolicy::default());
+
+        assert!(matches!(
+            manager.reload("runner"),
+            Err(PluginError::PermissionDenied(_))
+        ));
+        assert_eq!(manager.plugin_names(), vec!["cpu_temp_widget".to_string()]);
+        assert!(manager.processor_plugins.is_empty());
+    }
+
+    #[test]
+    fn test_reload_all_reports_failures_and_continues() {
+        let mut manager = PluginManager::new();
+        let mut policy = PermissionPolicy::default();
+        policy.grants.insert(
+            "runner".to_string(),
+            vec![Permission::ReadSystemMetrics, Permission::ExecuteCommands],
+        );
+        policy
+            .allowed_commands
+            .insert("runner".to_string(), vec!["sensors".to_string()]);
+        manager.set_policy(policy);
+        manager
+            .register_processor_plugin(Box::new(CommandPlugin))
+            .unwrap();
+        manager
+            .register_widget_plugin(Box::new(CpuTempWidgetPlugin::new()))
+            .unwrap();
+        manager.set_policy(PermissionPolicy::default());
+
+        let (reloaded, failed) = manager.reload_all().unwrap();
+        assert_eq!(reloaded, 1);
+        assert_eq!(failed.len(), 1);
+        assert!(matches!(failed[0], PluginError::PermissionDenied(_)));
+    }
+
+    #[test]
+    fn test_html_exporter() {
+        let mut manager = PluginManager::new();
+        manager
+            .register_export_plugin(Box::new(HtmlExporterPlugin::new()))
+            .unwrap();
+        assert_eq!(
+            manager.exporter_formats(),
+            vec![("html_exporter".to_string(), "html".to_string())]
+        );
+        let snapshot = SystemSnapshot {
+            cpu_usage: 42.0,
+            memory_usage: 91.0,
+            network_rx: 0.0,
+            network_tx: 0.0,
+            process_count: 1,
+            uptime_seconds: 3600,
+            load_average: (0.1, 0.2, 0.3),
+            cpu_temperature: None,
+            timestamp: 0,
+            top_processes: vec![ProcessSummary {
+                pid: 1,
+                user: "root".to_string(),
+                command: "init <script>".to_string(),
+                cpu_percent: 1.0,
+                memory_bytes: 1024,
+            }],
+        };
+        let html = HtmlExporterPlugin::new().render_html(&snapshot);
+        assert!(html.contains("init &lt;script&gt;"));
+        assert!(html.contains("42.0%"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn test_update_interval_throttling() {
+        let mut manager = PluginManager::new();
+        manager
+            .load_config_str("[plugins.slow]\nupdate_interval = 1000\n")
+            .unwrap();
+        let t0 = Instant::now();
+        assert!(manager.take_due("slow", t0));
+        assert!(!manager.take_due("slow", t0 + Duration::from_millis(500)));
+        assert!(manager.take_due("slow", t0 + Duration::from_millis(1000)));
+        // No interval configured: due on every refresh
+        assert!(manager.take_due("fast", t0));
+        assert!(manager.take_due("fast", t0));
+    }
+
     #[test]
     fn test_cpu_temp_plugin() {
         let plugin = CpuTempWidgetPlugin::new();
@@ -0,0 +1,102 @@
+// Lyvoxa — Stellar system monitor
+// Copyright (c) 2025 Rezky Nightky 2025
+// Licensed under GPL-3.0-or-later. See LICENSE in project root.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+// Length of the fading trail behind each drop, in rows
+const TRAIL: i32 = 12;
+
+/// Falling-glyph animation shown after the configured inactivity period
+pub struct Rain {
+    // Head row per column; negative = still above the screen (staggers the start)
+    heads: Vec<i32>,
+    height: i32,
+    frame: u64,
+}
+
+impl Rain {
+    pub fn new() -> Self {
+        Self {
+            heads: Vec::new(),
+            height: 0,
+            frame: 0,
+        }
+    }
+
+    /// Advance one frame, re-seeding the columns when the terminal size changed
+    pub fn step(&mut self, width: u16, height: u16) {
+        let height = height as i32;
+        if self.heads.len() != width as usize || self.height != height {
+            self.height = height;
+            self.heads = (0..width).map(|_| -random_below(height.max(1))).collect();
+        }
+        for head in &mut self.heads {
+            *head += 1;
+            if *head - TRAIL > self.height {
+                *head = -random_below(self.height.max(1));
+            }
+        }
+        self.frame = self.frame.wrapping_add(1);
+    }
+
+    /// Matrix rain gets katakana and digits; other themes a quieter ASCII starfall
+    pub fn render(&self, f: &mut Frame, area: Rect, color: Color, bg: Color, matrix: bool) {
+        let glyphs: &[char] = if matrix {
+            &[
+                'ｱ', 'ｲ', 'ｳ', 'ｴ', 'ｵ', 'ｶ', 'ｷ', 'ｸ', 'ｹ', 'ｺ', 'ｻ', 'ｼ', 'ｽ', 'ｾ', 'ｿ', '0',
+                '1', '2', '3', '4', '5', '6', '7', '8', '9',
+            ]
+        } else {
+            &['.', '*', '+', '\'', '`']
+        };
+        let mut lines = Vec::with_capacity(area.height as usize);
+        for y in 0..area.height as i32 {
+            let mut spans = Vec::with_capacity(area.width as usize);
+            for (x, &head) in self.heads.iter().enumerate().take(area.width as usize) {
+                let dist = head - y;
+                let span = if dist == 0 {
+                    Span::styled(
+                        glyph(glyphs, x, y, self.frame).to_string(),
+                        Style::default()
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else if (1..TRAIL).contains(&dist) {
+                    // The far half of the trail is dimmed so drops fade out
+                    let style = if dist > TRAIL / 2 {
+                        Style::default().fg(color).add_modifier(Modifier::DIM)
+                    } else {
+                        Style::default().fg(color)
+                    };
+                    Span::styled(glyph(glyphs, x, y, self.frame).to_string(), style)
+                } else {
+                    Span::raw(" ")
+                };
+                spans.push(span);
+            }
+            lines.push(Line::from(spans));
+        }
+        f.render_widget(Paragraph::new(lines).style(Style::default().bg(bg)), area);
+    }
+}
+
+// Glyphs flicker slowly: each cell changes every few frames rather than every frame
+fn glyph(glyphs: &[char], x: usize, y: i32, frame: u64) -> char {
+    let seed = (x as u64)
+        .wrapping_mul(31)
+        .wrapping_add(y as u64)
+        .wrapping_mul(17)
+        .wrapping_add(frame / 4);
+    glyphs[(seed % glyphs.len() as u64) as usize]
+}
+
+fn random_below(n: i32) -> i32 {
+    (rand::random::<u32>() % n.max(1) as u32) as i32
+}
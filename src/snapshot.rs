@@ -0,0 +1,249 @@
+// Lyvoxa — Stellar system monitor
+// Copyright (c) 2025 Rezky Nightky 2025
+// Licensed under GPL-3.0-or-later. See LICENSE in project root.
+
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, io::Read, path::Path};
+
+// Process CPU must move this many percentage points to count as a significant change
+const CPU_CHANGE_POINTS: f32 = 10.0;
+// Process memory must grow/shrink by this fraction to count as a significant change
+const MEM_CHANGE_RATIO: f64 = 0.25;
+
+/// Exported system snapshot (F11), also the input format for `--diff`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub timestamp: String,
+    pub version: String,
+    pub theme: String,
+    pub system_metrics: SnapshotMetrics,
+    #[serde(default)]
+    pub top_processes: Vec<SnapshotProcess>,
+    // Set only when exported from frozen data (manual refresh mode): when the data was
+    // captured and how many seconds old it was at export time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_captured_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stale_seconds: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotMetrics {
+    pub cpu_usage_percent: f64,
+    pub memory_usage_percent: f64,
+    pub network_rx_bytes_per_sec: f64,
+    pub network_tx_bytes_per_sec: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotProcess {
+    pub pid: u32,
+    pub user: String,
+    pub command: String,
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+}
+
+/// Per-process difference between two snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessChange {
+    Appeared(SnapshotProcess),
+    Disappeared(SnapshotProcess),
+    Changed {
+        before: SnapshotProcess,
+        after: SnapshotProcess,
+    },
+}
+
+impl Snapshot {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| String::from("{}"))
+    }
+
+    /// Load a snapshot export, transparently inflating `.json.gz` files
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let raw = fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        // gzip magic rather than the extension, so renamed files still work
+        let text = if raw.starts_with(&[0x1f, 0x8b]) {
+            let mut out = String::new();
+            flate2::read::GzDecoder::new(raw.as_slice())
+                .read_to_string(&mut out)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+            out
+        } else {
+            String::from_utf8(raw).map_err(|e| format!("{}: {}", path.display(), e))?
+        };
+        serde_json::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+/// Appeared/disappeared PIDs plus those whose CPU or memory moved significantly
+pub fn diff_processes(before: &Snapshot, after: &Snapshot) -> Vec<ProcessChange> {
+    let old: HashMap<u32, &SnapshotProcess> =
+        before.top_processes.iter().map(|p| (p.pid, p)).collect();
+    let new: HashMap<u32, &SnapshotProcess> =
+        after.top_processes.iter().map(|p| (p.pid, p)).collect();
+
+    let mut changes = Vec::new();
+    for p in &after.top_processes {
+        match old.get(&p.pid) {
+            None => changes.push(ProcessChange::Appeared(p.clone())),
+            Some(prev) if is_significant(prev, p) => changes.push(ProcessChange::Changed {
+                before: (*prev).clone(),
+                after: p.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for p in &before.top_processes {
+        if !new.contains_key(&p.pid) {
+            changes.push(ProcessChange::Disappeared(p.clone()));
+        }
+    }
+    changes
+}
+
+fn is_significant(before: &SnapshotProcess, after: &SnapshotProcess) -> bool {
+    if (after.cpu_percent - before.cpu_percent).abs() >= CPU_CHANGE_POINTS {
+        return true;
+    }
+    let base = before.memory_bytes.max(1) as f64;
+    let delta = after.memory_bytes as f64 - before.memory_bytes as f64;
+    (delta / base).abs() >= MEM_CHANGE_RATIO
+}
+
+/// Human-readable report for `--diff`
+pub fn format_diff(before: &Snapshot, after: &Snapshot) -> String {
+    let fmt_bytes = |b: f64| humansize::format_size(b.max(0.0) as u64, humansize::DECIMAL);
+    let signed_bytes = |d: f64| {
+        let sign = if d < 0.0 { "-" } else { "+" };
+        format!("{}{}", sign, fmt_bytes(d.abs()))
+    };
+    let b = &before.system_metrics;
+    let a = &after.system_metrics;
+
+    let mut out = Vec::new();
+    out.push(format!(
+        "Before: {} (v{})",
+        before.timestamp, before.version
+    ));
+    out.push(format!("After:  {} (v{})", after.timestamp, after.version));
+    out.push(String::new());
+    out.push("System:".to_string());
+    out.push(format!(
+        "  CPU     {:>6.1}% -> {:>6.1}%  ({:+.1} pts)",
+        b.cpu_usage_percent,
+        a.cpu_usage_percent,
+        a.cpu_usage_percent - b.cpu_usage_percent
+    ));
+    out.push(format!(
+        "  Memory  {:>6.1}% -> {:>6.1}%  ({:+.1} pts)",
+        b.memory_usage_percent,
+        a.memory_usage_percent,
+        a.memory_usage_percent - b.memory_usage_percent
+    ));
+    out.push(format!(
+        "  Net RX  {}/s -> {}/s  ({}/s)",
+        fmt_bytes(b.network_rx_bytes_per_sec),
+        fmt_bytes(a.network_rx_bytes_per_sec),
+        signed_bytes(a.network_rx_bytes_per_sec - b.network_rx_bytes_per_sec)
+    ));
+    out.push(format!(
+        "  Net TX  {}/s -> {}/s  ({}/s)",
+        fmt_bytes(b.network_tx_bytes_per_sec),
+        fmt_bytes(a.network_tx_bytes_per_sec),
+        signed_bytes(a.network_tx_bytes_per_sec - b.network_tx_bytes_per_sec)
+    ));
+    out.push(String::new());
+
+    // Snapshots only carry the top processes, so "disappeared" may also mean "fell out of the top"
+    out.push("Processes (exported top entries only):".to_string());
+    let changes = diff_processes(before, after);
+    if changes.is_empty() {
+        out.push("  no significant changes".to_string());
+    }
+    for change in &changes {
+        match change {
+            ProcessChange::Appeared(p) => out.push(format!(
+                "  + {:>7} {:<12} {:>5.1}% {:>10}  {}",
+                p.pid,
+                p.user,
+                p.cpu_percent,
+                fmt_bytes(p.memory_bytes as f64),
+                p.command
+            )),
+            ProcessChange::Disappeared(p) => out.push(format!(
+                "  - {:>7} {:<12} {:>5.1}% {:>10}  {}",
+                p.pid,
+                p.user,
+                p.cpu_percent,
+                fmt_bytes(p.memory_bytes as f64),
+                p.command
+            )),
+            ProcessChange::Changed { before, after } => out.push(format!(
+                "  ~ {:>7} {:<12} cpu {:.1}% -> {:.1}%  mem {} -> {}  {}",
+                after.pid,
+                after.user,
+                before.cpu_percent,
+                after.cpu_percent,
+                fmt_bytes(before.memory_bytes as f64),
+                fmt_bytes(after.memory_bytes as f64),
+                after.command
+            )),
+        }
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proc_(pid: u32, cpu: f32, mem: u64) -> SnapshotProcess {
+        SnapshotProcess {
+            pid,
+            user: "root".to_string(),
+            command: format!("cmd{}", pid),
+            cpu_percent: cpu,
+            memory_bytes: mem,
+        }
+    }
+
+    fn snap(procs: Vec<SnapshotProcess>) -> Snapshot {
+        Snapshot {
+            timestamp: "2025-01-01 00:00:00".to_string(),
+            version: "test".to_string(),
+            theme: "Stellar".to_string(),
+            system_metrics: SnapshotMetrics::default(),
+            top_processes: procs,
+            data_captured_at: None,
+            stale_seconds: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_processes() {
+        let before = snap(vec![
+            proc_(1, 5.0, 1000),
+            proc_(2, 1.0, 1000),
+            proc_(3, 50.0, 1000),
+        ]);
+        let after = snap(vec![
+            proc_(1, 6.0, 1100),
+            proc_(3, 90.0, 1000),
+            proc_(4, 1.0, 10),
+        ]);
+        let changes = diff_processes(&before, &after);
+        assert_eq!(changes.len(), 3);
+        assert!(matches!(&changes[0], ProcessChange::Changed { after, .. } if after.pid == 3));
+        assert!(matches!(&changes[1], ProcessChange::Appeared(p) if p.pid == 4));
+        assert!(matches!(&changes[2], ProcessChange::Disappeared(p) if p.pid == 2));
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let s = snap(vec![proc_(7, 1.5, 42)]);
+        let parsed: Snapshot = serde_json::from_str(&s.to_json()).unwrap();
+        assert_eq!(parsed.top_processes, s.top_processes);
+    }
+}
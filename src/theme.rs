@@ -16,10 +16,9 @@ pub struct Theme {
     pub fg: Color,
     pub bg: Color,
     pub accent: Color,
-    #[allow(dead_code)]
-    pub cpu: Color, // Kept for future chart colors
-    #[allow(dead_code)]
-    pub mem: Color, // Kept for future chart colors
+    pub cpu: Color,  // CPU chart color
+    pub mem: Color,  // Memory chart color
+    pub swap: Color, // Swap chart line color
     pub net_rx: Color,
     pub net_tx: Color,
     pub table_header: Color,
@@ -33,6 +32,9 @@ pub struct Theme {
     pub cpu_label: Color,
     pub mem_label: Color,
     pub swap_label: Color,
+    // Severity colors
+    pub warn: Color,     // Near-limit / degraded
+    pub critical: Color, // Over-limit / alert
 }
 
 // Helper function for task status colors
@@ -40,6 +42,23 @@ pub fn get_run_color(_theme: &Theme) -> Color {
     Color::Green
 }
 
+// Helper function for threshold coloring: normal below warn, warn up to critical, then critical
+pub fn get_severity_color(
+    theme: &Theme,
+    percent: f64,
+    warn_at: f64,
+    critical_at: f64,
+    normal: Color,
+) -> Color {
+    if percent >= critical_at {
+        theme.critical
+    } else if percent >= warn_at {
+        theme.warn
+    } else {
+        normal
+    }
+}
+
 impl Theme {
     pub fn palette(kind: ThemeKind) -> Self {
         match kind {
@@ -49,6 +68,7 @@ impl Theme {
                 accent: Color::Cyan,
                 cpu: Color::Yellow,
                 mem: Color::Green,
+                swap: Color::Magenta,
                 net_rx: Color::LightCyan,
                 net_tx: Color::LightMagenta,
                 table_header: Color::Cyan,
@@ -60,6 +80,8 @@ impl Theme {
                 cpu_label: Color::Cyan,
                 mem_label: Color::Green,
                 swap_label: Color::Magenta,
+                warn: Color::Yellow,
+                critical: Color::LightRed,
             },
             ThemeKind::Stellar => Self {
                 fg: Color::Rgb(200, 210, 255),
@@ -67,6 +89,7 @@ impl Theme {
                 accent: Color::Rgb(120, 100, 255),
                 cpu: Color::Rgb(255, 210, 90),
                 mem: Color::Rgb(120, 255, 160),
+                swap: Color::Rgb(255, 150, 240),
                 net_rx: Color::Rgb(120, 240, 255),
                 net_tx: Color::Rgb(255, 120, 240),
                 table_header: Color::Rgb(140, 120, 255),
@@ -78,6 +101,8 @@ impl Theme {
                 cpu_label: Color::Rgb(150, 200, 255),
                 mem_label: Color::Rgb(120, 255, 180),
                 swap_label: Color::Rgb(255, 150, 240),
+                warn: Color::Rgb(255, 190, 80),     // Stellar amber
+                critical: Color::Rgb(255, 80, 100), // Stellar crimson
             },
             ThemeKind::Matrix => Self {
                 fg: Color::Rgb(180, 255, 180),
@@ -85,6 +110,7 @@ impl Theme {
                 accent: Color::Rgb(0, 255, 120),
                 cpu: Color::Rgb(160, 255, 160),
                 mem: Color::Rgb(0, 200, 80),
+                swap: Color::Rgb(150, 255, 100),
                 net_rx: Color::Rgb(100, 255, 180),
                 net_tx: Color::Rgb(0, 180, 120),
                 table_header: Color::Rgb(0, 255, 120),
@@ -96,6 +122,8 @@ impl Theme {
                 cpu_label: Color::Rgb(100, 255, 100),
                 mem_label: Color::Rgb(0, 255, 150),
                 swap_label: Color::Rgb(150, 255, 100),
+                warn: Color::Rgb(230, 255, 0),     // Matrix amber-green
+                critical: Color::Rgb(255, 40, 40), // Matrix red alert
             },
         }
     }